@@ -5,6 +5,9 @@
 use config::{Config, ConfigError, Environment, File};
 use serde::{Deserialize, Serialize};
 
+use crate::sim::SimConfig;
+use crate::sim::tictactoe::ai::AiConfig;
+
 /// Window configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowConfig {
@@ -31,6 +34,12 @@ pub struct AppConfig {
     pub profile: String,
     /// Window configuration
     pub window: WindowConfig,
+    /// Simulations to run and their parameters
+    #[serde(default)]
+    pub sim: SimConfig,
+    /// Computer opponent for tic-tac-toe
+    #[serde(default)]
+    pub ai: AiConfig,
 }
 
 impl AppConfig {
@@ -114,6 +123,8 @@ impl Default for AppConfig {
                 decorated: true,
                 vsync: true,
             },
+            sim: SimConfig::default(),
+            ai: AiConfig::default(),
         })
     }
 }
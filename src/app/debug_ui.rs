@@ -4,6 +4,9 @@ use std::time::Instant;
 
 use sysinfo::System;
 
+use crate::sim::WorldAction;
+use crate::sim::tictactoe::Variant;
+
 /// Mouse debug information
 pub struct MouseDebugInfo<'a> {
     pub cursor_pos: Option<winit::dpi::PhysicalPosition<f64>>,
@@ -11,6 +14,25 @@ pub struct MouseDebugInfo<'a> {
     pub last_click_info: &'a Option<String>,
 }
 
+/// Commands requested from the debug UI, applied by the App after the frame is drawn
+#[derive(Debug, Clone)]
+pub enum DebugCommand {
+    /// Apply a (recordable) world action
+    World(WorldAction),
+    /// Start recording a replay
+    StartRecording,
+    /// Stop recording and save the replay
+    StopRecording,
+    /// Step back to the previous rewind frame
+    StepBack,
+    /// Jump to the rewind frame at or before a tick
+    RewindTo(u64),
+    /// Take back the last move (and the computer's reply)
+    Undo,
+    /// Replay the last undone move (and the computer's reply)
+    Redo,
+}
+
 /// Debug UI state for toggling different panels
 #[derive(Debug, Clone)]
 pub struct DebugUIState {
@@ -23,6 +45,7 @@ pub struct DebugUIState {
     pub show_input_system: bool,
     frame_times: Vec<f32>,
     last_frame_time: Instant,
+    commands: Vec<DebugCommand>,
 }
 
 impl Default for DebugUIState {
@@ -37,6 +60,7 @@ impl Default for DebugUIState {
             show_input_system: true,
             frame_times: Vec::with_capacity(100),
             last_frame_time: Instant::now(),
+            commands: Vec::new(),
         }
     }
 }
@@ -48,6 +72,11 @@ impl DebugUIState {
         self.show_window = !self.show_window;
     }
 
+    /// Takes the commands requested since the last call
+    pub fn take_commands(&mut self) -> Vec<DebugCommand> {
+        std::mem::take(&mut self.commands)
+    }
+
     /// Updates frame timing information
     pub fn update_frame_time(&mut self) {
         let now = Instant::now();
@@ -258,8 +287,211 @@ impl DebugUIState {
                             ui.heading("World State");
                             ui.label(format!("Simulation time: {:.2}s", world.sim_time()));
                             ui.label(format!("Tick count: {}", world.tick_count()));
-                            ui.label(format!("Time scale: {:.2}x", world.time_scale()));
-                            ui.label(format!("Paused: {}", world.is_paused()));
+                            ui.label(format!(
+                                "Tick order: {}",
+                                world.schedule().collect::<Vec<_>>().join(" → ")
+                            ));
+
+                            let mut paused = world.is_paused();
+                            if ui.checkbox(&mut paused, "Paused").changed() {
+                                self.commands
+                                    .push(DebugCommand::World(WorldAction::SetPaused(paused)));
+                            }
+                            let mut time_scale = world.time_scale();
+                            if ui
+                                .add(
+                                    egui::Slider::new(&mut time_scale, 0.0..=4.0)
+                                        .text("Time scale"),
+                                )
+                                .changed()
+                            {
+                                self.commands
+                                    .push(DebugCommand::World(WorldAction::SetTimeScale(
+                                        time_scale,
+                                    )));
+                            }
+                            ui.label(format!(
+                                "Fixed step: {:.2}ms (alpha {:.2})",
+                                world.timestep().step * 1000.0,
+                                world.interpolation_alpha()
+                            ));
+
+                            // Per-simulation pause and time multiplier
+                            egui::Grid::new("simulation_controls")
+                                .num_columns(3)
+                                .show(ui, |ui| {
+                                    for sim in world.simulations() {
+                                        let name = sim.name();
+                                        let Some(control) = world.simulation_control(name) else {
+                                            continue;
+                                        };
+
+                                        ui.label(name);
+                                        let mut paused = control.paused;
+                                        if ui.checkbox(&mut paused, "Paused").changed() {
+                                            self.commands.push(DebugCommand::World(
+                                                WorldAction::SetSimulationPaused {
+                                                    name: name.to_string(),
+                                                    paused,
+                                                },
+                                            ));
+                                        }
+                                        let mut scale = control.time_scale;
+                                        if ui
+                                            .add(
+                                                egui::Slider::new(&mut scale, 0.0..=10.0)
+                                                    .text("Speed"),
+                                            )
+                                            .changed()
+                                        {
+                                            self.commands.push(DebugCommand::World(
+                                                WorldAction::SetSimulationTimeScale {
+                                                    name: name.to_string(),
+                                                    scale,
+                                                },
+                                            ));
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+
+                            // Per-simulation tick timings
+                            ui.label("Tick timings (µs):");
+                            egui::Grid::new("simulation_profile")
+                                .num_columns(5)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for header in ["Simulation", "Mean", "p95", "Max", "Ticks"] {
+                                        ui.label(header);
+                                    }
+                                    ui.end_row();
+                                    for profile in world.profile() {
+                                        ui.label(&profile.name);
+                                        for duration in [profile.mean, profile.p95, profile.max] {
+                                            ui.label(format!(
+                                                "{:.1}",
+                                                duration.as_secs_f64() * 1.0e6
+                                            ));
+                                        }
+                                        ui.label(profile.samples.to_string());
+                                        ui.end_row();
+                                    }
+                                });
+
+                            ui.horizontal(|ui| {
+                                let tictactoe = world.tictactoe();
+                                if ui
+                                    .add_enabled(
+                                        tictactoe.is_some_and(|t| t.can_undo()),
+                                        egui::Button::new("Undo"),
+                                    )
+                                    .clicked()
+                                {
+                                    self.commands.push(DebugCommand::Undo);
+                                }
+                                if ui
+                                    .add_enabled(
+                                        tictactoe.is_some_and(|t| t.can_redo()),
+                                        egui::Button::new("Redo"),
+                                    )
+                                    .clicked()
+                                {
+                                    self.commands.push(DebugCommand::Redo);
+                                }
+                                if ui.button("Reset board").clicked() {
+                                    self.commands
+                                        .push(DebugCommand::World(WorldAction::ResetBoard));
+                                }
+                                if ui.button("Reset all").clicked() {
+                                    self.commands
+                                        .push(DebugCommand::World(WorldAction::ResetAll));
+                                }
+                            });
+
+                            if let Some(tictactoe) = world.tictactoe() {
+                                let variant = tictactoe.variant();
+                                if tictactoe.rules().pieces(tictactoe.current_player()).len() > 1 {
+                                    ui.label(format!(
+                                        "Rules: {variant} (right-click places the other piece)"
+                                    ));
+                                } else {
+                                    ui.label(format!("Rules: {variant}"));
+                                }
+                            }
+
+                            // Board notation, for pasting into bug reports; it only
+                            // describes standard games
+                            if let Some(tictactoe) = world
+                                .tictactoe()
+                                .filter(|t| t.variant() == Variant::Standard)
+                            {
+                                let position = tictactoe.to_position();
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("Position: {position}"));
+                                    if ui.small_button("Copy").clicked() {
+                                        ui.ctx().copy_text(position.clone());
+                                    }
+                                });
+                                let moves = tictactoe.to_moves();
+                                ui.horizontal(|ui| {
+                                    ui.label(format!("Moves: {moves}"));
+                                    if ui.small_button("Copy").clicked() {
+                                        ui.ctx().copy_text(moves.clone());
+                                    }
+                                });
+                            }
+
+                            // Replay controls
+                            if world.is_replaying() {
+                                ui.label("Replay: playing");
+                            } else if world.is_recording() {
+                                ui.horizontal(|ui| {
+                                    ui.label("Replay: recording");
+                                    if ui.button("Stop & save").clicked() {
+                                        self.commands.push(DebugCommand::StopRecording);
+                                    }
+                                });
+                            } else if ui.button("Start recording").clicked() {
+                                self.commands.push(DebugCommand::StartRecording);
+                            }
+                            if let Some(tick) = world.replay_divergence() {
+                                ui.colored_label(
+                                    egui::Color32::RED,
+                                    format!("Replay diverged at tick {tick}"),
+                                );
+                            }
+
+                            // Desync detection
+                            if let Some(latest) = world.checksum_history().latest() {
+                                ui.label(format!(
+                                    "Checksum @{}: {:016x}",
+                                    latest.tick, latest.checksum
+                                ));
+                            }
+
+                            // Rewind controls
+                            if let Some(rewind) = world.rewind_buffer()
+                                && let (Some(oldest), Some(newest)) =
+                                    (rewind.oldest_tick(), rewind.newest_tick())
+                            {
+                                ui.label(format!(
+                                    "Rewind: {} frames, {:.1} MiB",
+                                    rewind.len(),
+                                    rewind.memory_usage() as f64 / (1024.0 * 1024.0)
+                                ));
+                                ui.horizontal(|ui| {
+                                    if ui.button("Step back").clicked() {
+                                        self.commands.push(DebugCommand::StepBack);
+                                    }
+                                    let mut tick = world.tick_count().clamp(oldest, newest);
+                                    if ui
+                                        .add(egui::Slider::new(&mut tick, oldest..=newest))
+                                        .changed()
+                                    {
+                                        self.commands.push(DebugCommand::RewindTo(tick));
+                                    }
+                                });
+                            }
                             ui.separator();
                         }
 
@@ -10,59 +10,93 @@ pub struct BoardLayout {
     pub center_y: f32,
     pub cell_size: f32,
     pub line_thickness: f32,
+    /// Number of board columns
+    pub columns: usize,
+    /// Number of board rows
+    pub rows: usize,
 }
 
 impl BoardLayout {
-    /// Creates a centered board layout for the given screen size
-    pub fn centered(screen_width: f32, screen_height: f32) -> Self {
-        let size = screen_width.min(screen_height) * 0.6;
+    /// Creates a centered layout for a board of `columns` x `rows` cells, scaled to
+    /// fill 60% of the screen in its tighter dimension
+    pub fn centered(screen_width: f32, screen_height: f32, columns: usize, rows: usize) -> Self {
+        let columns = columns.max(1);
+        let rows = rows.max(1);
+        let cell_size =
+            (screen_width * 0.6 / columns as f32).min(screen_height * 0.6 / rows as f32);
         Self {
             center_x: screen_width / 2.0,
             center_y: screen_height / 2.0,
-            cell_size: size / 3.0,
+            cell_size,
             line_thickness: 4.0,
+            columns,
+            rows,
         }
     }
 
-    /// Gets the screen position for a board cell (row, col both 0-2)
-    pub fn cell_center(&self, row: usize, col: usize) -> [f32; 2] {
-        let board_width = self.cell_size * 3.0;
-        let top_left_x = self.center_x - board_width / 2.0;
-        let top_left_y = self.center_y - board_width / 2.0;
+    /// Returns the board's on-screen width and height
+    pub fn board_size(&self) -> [f32; 2] {
+        [
+            self.cell_size * self.columns as f32,
+            self.cell_size * self.rows as f32,
+        ]
+    }
 
+    /// Returns the screen position of the board's top-left corner
+    fn top_left(&self) -> [f32; 2] {
+        let [width, height] = self.board_size();
+        [self.center_x - width / 2.0, self.center_y - height / 2.0]
+    }
+
+    /// Gets the screen position for a board cell
+    pub fn cell_center(&self, row: usize, col: usize) -> [f32; 2] {
+        let [left, top] = self.top_left();
         [
-            top_left_x + (col as f32 + 0.5) * self.cell_size,
-            top_left_y + (row as f32 + 0.5) * self.cell_size,
+            left + (col as f32 + 0.5) * self.cell_size,
+            top + (row as f32 + 0.5) * self.cell_size,
         ]
     }
 
     /// Converts screen coordinates to board cell (row, col)
     /// Returns None if outside the board
     pub fn screen_to_cell(&self, screen_x: f32, screen_y: f32) -> Option<(usize, usize)> {
-        let board_width = self.cell_size * 3.0;
-        let top_left_x = self.center_x - board_width / 2.0;
-        let top_left_y = self.center_y - board_width / 2.0;
+        let [left, top] = self.top_left();
+        let [width, height] = self.board_size();
 
-        let rel_x = screen_x - top_left_x;
-        let rel_y = screen_y - top_left_y;
+        let rel_x = screen_x - left;
+        let rel_y = screen_y - top;
 
-        if rel_x < 0.0 || rel_y < 0.0 || rel_x >= board_width || rel_y >= board_width {
+        if rel_x < 0.0 || rel_y < 0.0 || rel_x >= width || rel_y >= height {
             return None;
         }
 
         let col = (rel_x / self.cell_size) as usize;
         let row = (rel_y / self.cell_size) as usize;
 
-        if row < 3 && col < 3 {
+        if row < self.rows && col < self.columns {
             Some((row, col))
         } else {
             None
         }
     }
 
+    /// Returns the layout of the same board seen as groups of `block` x `block`
+    /// cells, e.g. the sub-boards of an ultimate tic-tac-toe grid
+    pub fn blocks(&self, block: usize) -> Self {
+        let block = block.max(1);
+        Self {
+            center_x: self.center_x,
+            center_y: self.center_y,
+            cell_size: self.cell_size * block as f32,
+            line_thickness: self.line_thickness * 2.0,
+            columns: self.columns / block,
+            rows: self.rows / block,
+        }
+    }
+
     /// Converts world coordinates to screen coordinates
     /// World coordinates are board-relative, centered at (0, 0), in units of cell_size
-    /// Example: world position (0, -0.5) maps to the first horizontal grid line
+    /// Example: on a 3x3 board, world position (0, -0.5) maps to the first horizontal grid line
     pub fn world_to_screen(&self, world_pos: [f32; 2]) -> [f32; 2] {
         [
             self.center_x + world_pos[0] * self.cell_size,
@@ -83,20 +117,19 @@ impl BoardLayout {
 /// Generates lines for the tic-tac-toe board grid
 pub fn generate_board_grid(layout: &BoardLayout) -> Vec<Line> {
     let mut lines = Vec::new();
-    let board_width = layout.cell_size * 3.0;
-    let left = layout.center_x - board_width / 2.0;
-    let right = layout.center_x + board_width / 2.0;
-    let top = layout.center_y - board_width / 2.0;
-    let bottom = layout.center_y + board_width / 2.0;
-
-    // Horizontal lines (2)
-    for i in 1..3 {
+    let [left, top] = layout.top_left();
+    let [width, height] = layout.board_size();
+    let right = left + width;
+    let bottom = top + height;
+
+    // Interior horizontal lines (rows - 1)
+    for i in 1..layout.rows {
         let y = top + i as f32 * layout.cell_size;
         lines.push(Line::new([left, y], [right, y], layout.line_thickness));
     }
 
-    // Vertical lines (2)
-    for i in 1..3 {
+    // Interior vertical lines (columns - 1)
+    for i in 1..layout.columns {
         let x = left + i as f32 * layout.cell_size;
         lines.push(Line::new([x, top], [x, bottom], layout.line_thickness));
     }
@@ -104,6 +137,88 @@ pub fn generate_board_grid(layout: &BoardLayout) -> Vec<Line> {
     lines
 }
 
+/// Generates lines for a grid split into `block` x `block` sub-boards, with the
+/// sub-board borders drawn twice as thick as the lines inside them
+pub fn generate_nested_grid(layout: &BoardLayout, block: usize) -> Vec<Line> {
+    let block = block.max(1);
+    let mut lines = Vec::new();
+    let [left, top] = layout.top_left();
+    let [width, height] = layout.board_size();
+    let right = left + width;
+    let bottom = top + height;
+    let thickness = |i: usize| {
+        if i.is_multiple_of(block) {
+            layout.line_thickness * 2.0
+        } else {
+            layout.line_thickness * 0.5
+        }
+    };
+
+    for i in 1..layout.rows {
+        let y = top + i as f32 * layout.cell_size;
+        lines.push(Line::new([left, y], [right, y], thickness(i)));
+    }
+    for i in 1..layout.columns {
+        let x = left + i as f32 * layout.cell_size;
+        lines.push(Line::new([x, top], [x, bottom], thickness(i)));
+    }
+
+    lines
+}
+
+/// Generates a square outline just inside the given cell
+pub fn generate_outline(layout: &BoardLayout, row: usize, col: usize) -> Vec<Line> {
+    let center = layout.cell_center(row, col);
+    let half = layout.cell_size * 0.46;
+    let corners = [
+        [center[0] - half, center[1] - half],
+        [center[0] + half, center[1] - half],
+        [center[0] + half, center[1] + half],
+        [center[0] - half, center[1] + half],
+    ];
+
+    (0..corners.len())
+        .map(|i| {
+            Line::new(
+                corners[i],
+                corners[(i + 1) % corners.len()],
+                layout.line_thickness,
+            )
+        })
+        .collect()
+}
+
+/// Generates a line striking through cells `from` to `to`, overshooting their
+/// centers by a third of a cell at each end
+pub fn generate_strike_through(
+    layout: &BoardLayout,
+    from: (usize, usize),
+    to: (usize, usize),
+) -> Line {
+    let start = layout.cell_center(from.0, from.1);
+    let end = layout.cell_center(to.0, to.1);
+    let [dx, dy] = [end[0] - start[0], end[1] - start[1]];
+    let length = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+    let overshoot = layout.cell_size / 3.0;
+    let [ox, oy] = [dx / length * overshoot, dy / length * overshoot];
+    Line::new(
+        [start[0] - ox, start[1] - oy],
+        [end[0] + ox, end[1] + oy],
+        layout.line_thickness * 2.0,
+    )
+}
+
+/// Generates a single thick line covering most of a cell, for tinting it
+pub fn generate_cell_fill(layout: &BoardLayout, row: usize, col: usize) -> Line {
+    let center = layout.cell_center(row, col);
+    let half = layout.cell_size * 0.45;
+    Line::new(
+        [center[0] - half, center[1]],
+        [center[0] + half, center[1]],
+        half * 2.0,
+    )
+}
+
 /// Generates lines for an X symbol at the given cell
 pub fn generate_x(layout: &BoardLayout, row: usize, col: usize) -> Vec<Line> {
     let center = layout.cell_center(row, col);
@@ -1,6 +1,7 @@
-//! Line renderer for drawing thick white lines
+//! Line renderer for drawing thick lines
 //!
-//! This shader renders lines as thick white segments on a transparent background.
+//! This shader renders lines as thick segments, white unless given a color, on a
+//! transparent background.
 //! Lines are represented as quads (two triangles) to support variable thickness.
 
 use wgpu::{
@@ -99,6 +100,12 @@ impl Line {
         }
     }
 
+    /// Returns the line drawn in `color` instead of white
+    pub fn with_color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+
     /// Generates vertices for this line as a quad
     fn to_vertices(&self) -> Vec<Vertex> {
         let dx = self.to[0] - self.from[0];
@@ -168,6 +175,11 @@ impl LineRenderer {
         self.lines.push(Line::new(from, to, thickness));
     }
 
+    /// Adds a colored line to be rendered
+    pub fn draw_colored_line(&mut self, line: Line) {
+        self.lines.push(line);
+    }
+
     /// Clears all lines
     pub fn clear(&mut self) {
         self.lines.clear();
@@ -14,6 +14,6 @@ mod shader_system;
 mod window;
 
 pub use config::{AppConfig, WindowConfig};
-pub use debug_ui::DebugUIState;
+pub use debug_ui::{DebugCommand, DebugUIState};
 pub use runner::App;
 pub use window::window_attributes_from_config;
@@ -31,11 +31,6 @@ impl Renderer {
         &self.config
     }
 
-    /// Initialize vines in the leaf simulation (call once after world is created)
-    pub fn init_leaf_vines(&mut self, world: &mut World) {
-        self.sim_renderer.init_vines(world);
-    }
-
     /// Creates a new renderer for the given window
     pub async fn new(window: Arc<Window>) -> anyhow::Result<Self> {
         info!("Initializing wgpu renderer");
@@ -174,6 +169,16 @@ impl Renderer {
         }
     }
 
+    /// Turns the perfect-play hint overlay on or off
+    pub fn set_show_hints(&mut self, show: bool) {
+        self.sim_renderer.set_show_hints(show);
+    }
+
+    /// Sets how far between the last tick and the next to draw the simulation
+    pub fn set_interpolation_alpha(&mut self, alpha: f32) {
+        self.sim_renderer.set_interpolation_alpha(alpha);
+    }
+
     /// Renders a frame with egui UI
     pub fn draw(
         &mut self,
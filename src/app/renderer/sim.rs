@@ -1,12 +1,14 @@
 use super::viewport::Viewport;
 use crate::app::{
-    ellipse_renderer::EllipseRenderer, geometry, line_renderer::LineRenderer,
+    ellipse_renderer::EllipseRenderer,
+    geometry,
+    line_renderer::{Line, LineRenderer},
     shader_system::ShaderRegistry,
 };
 use crate::sim::{
-    World,
-    leaf::Vine,
-    tictactoe::{Player, Tile},
+    TicTacToeSimulation, UltimateTicTacToeSimulation, World,
+    tictactoe::{Board, CellAnalysis, Clock, GameState, Player, Tile, Variant, Verdict},
+    ultimate,
 };
 use egui;
 use egui_wgpu;
@@ -20,12 +22,29 @@ const LEAF_COLORS: [[f32; 3]; 4] = [
     [0.3, 0.65, 0.4],  // Light green
 ];
 
+/// Colour leaves turn as they wilt
+const WILTED_LEAF_COLOR: [f32; 3] = [0.45, 0.3, 0.12];
+
+/// Hint tints for moves that win, draw and lose
+const HINT_WIN_COLOR: [f32; 3] = [0.1, 0.4, 0.15];
+const HINT_DRAW_COLOR: [f32; 3] = [0.35, 0.35, 0.1];
+const HINT_LOSS_COLOR: [f32; 3] = [0.45, 0.1, 0.1];
+
+/// Identifies a hinted position: the board, side to move and rules
+type HintKey = (Board, Player, Variant);
+
 /// Renderer for the simulation view
 pub struct SimRenderer {
     viewport: Viewport,
     shader_registry: ShaderRegistry,
     width: u32,
     height: u32,
+    /// Whether to tint empty cells by their perfect-play outcome
+    show_hints: bool,
+    /// Analysis of the last position hinted, reused until the position changes
+    hint_cache: Option<(HintKey, Vec<CellAnalysis>)>,
+    /// How far between the last tick and the next to draw leaves (0.0-1.0)
+    interpolation_alpha: f32,
 }
 
 impl SimRenderer {
@@ -61,9 +80,22 @@ impl SimRenderer {
             shader_registry,
             width,
             height,
+            show_hints: false,
+            hint_cache: None,
+            interpolation_alpha: 1.0,
         }
     }
 
+    /// Turns the perfect-play hint overlay on or off
+    pub fn set_show_hints(&mut self, show: bool) {
+        self.show_hints = show;
+    }
+
+    /// Sets how far between the last tick and the next to draw leaves
+    pub fn set_interpolation_alpha(&mut self, alpha: f32) {
+        self.interpolation_alpha = alpha.clamp(0.0, 1.0);
+    }
+
     /// Returns the texture ID for egui
     pub fn texture_id(&self) -> egui::TextureId {
         self.viewport.texture_id
@@ -95,45 +127,6 @@ impl SimRenderer {
         self.shader_registry.init_all(device, &config);
     }
 
-    /// Initialize vines in leaf simulation (call once before drawing)
-    /// Vines are stored in world coordinates (board-relative, centered at origin)
-    pub fn init_vines(&mut self, world: &mut World) {
-        use tracing::info;
-
-        if let Some(leaf_sim) = world.leaf_mut()
-            && leaf_sim.vines().is_empty()
-        {
-            info!("Initializing vines in world space (cell_size units, origin at board center)");
-
-            // Add horizontal vines (2 interior grid lines only, matching board grid)
-            // In world coords: -1.5 to +1.5 range represents the 3x3 board
-            for i in 1..3 {
-                let y = -1.5 + (i as f32); // i=1: -0.5, i=2: 0.5 (world coords)
-                let start = [-1.5, y];
-                let end = [1.5, y];
-                info!(
-                    "Adding horizontal vine {} at world y={}: {:?} → {:?}",
-                    i, y, start, end
-                );
-                leaf_sim.add_vine(Vine::new(start, end));
-            }
-
-            // Add vertical vines (2 interior grid lines only, matching board grid)
-            for i in 1..3 {
-                let x = -1.5 + (i as f32); // i=1: -0.5, i=2: 0.5 (world coords)
-                let start = [x, -1.5];
-                let end = [x, 1.5];
-                info!(
-                    "Adding vertical vine {} at world x={}: {:?} → {:?}",
-                    i, x, start, end
-                );
-                leaf_sim.add_vine(Vine::new(start, end));
-            }
-
-            info!("Vines initialized: {} total", leaf_sim.vines().len());
-        }
-    }
-
     /// Draws the simulation to the texture
     pub fn draw(
         &mut self,
@@ -142,13 +135,52 @@ impl SimRenderer {
         queue: &wgpu::Queue,
         world: &World,
     ) {
-        // Create board layout
-        let layout = geometry::BoardLayout::centered(self.width as f32, self.height as f32);
-
-        // Get tic-tac-toe simulation
-        let tictactoe = match world.tictactoe() {
-            Some(ttt) => ttt,
-            None => return, // No tic-tac-toe sim, nothing to render
+        // Ultimate tic-tac-toe takes precedence when both games are registered
+        let (layout, board_lines, scores) = if let Some(ultimate) = world.ultimate() {
+            let layout = geometry::BoardLayout::centered(
+                self.width as f32,
+                self.height as f32,
+                ultimate::SIZE,
+                ultimate::SIZE,
+            );
+            let lines = ultimate_lines(&layout, ultimate);
+            let score = ultimate.score();
+            (
+                layout,
+                lines,
+                [score.wins(Player::X), score.wins(Player::O)],
+            )
+        } else if let Some(tictactoe) = world.tictactoe() {
+            let board = tictactoe.board();
+            let layout = geometry::BoardLayout::centered(
+                self.width as f32,
+                self.height as f32,
+                board.width(),
+                board.height(),
+            );
+            let mut lines = if self.show_hints {
+                self.hint_lines(&layout, tictactoe)
+            } else {
+                Vec::new()
+            };
+            lines.extend(geometry::generate_board_grid(&layout));
+            for (row, row_tiles) in board.rows().enumerate() {
+                for (col, &tile) in row_tiles.iter().enumerate() {
+                    lines.extend(piece_lines(&layout, tile, row, col));
+                }
+            }
+            if let Some(line) = tictactoe.winning_line()
+                && let (Some(&first), Some(&last)) = (line.first(), line.last())
+            {
+                lines.push(geometry::generate_strike_through(&layout, first, last));
+            }
+            if let Some(clock) = tictactoe.clock() {
+                lines.extend(clock_lines(&layout, clock, tictactoe.current_player()));
+            }
+            let scores = [tictactoe.wins(Player::X), tictactoe.wins(Player::O)];
+            (layout, lines, scores)
+        } else {
+            return; // No tic-tac-toe sim, nothing to render
         };
 
         // Get leaf simulation
@@ -160,35 +192,13 @@ impl SimRenderer {
             .get_mut("line")
             .and_then(|r| r.as_any_mut().downcast_mut::<LineRenderer>())
         {
-            // Generate board grid lines
-            for line in geometry::generate_board_grid(&layout) {
-                line_renderer.draw_line(line.from, line.to, line.thickness);
-            }
-
-            // Generate pieces
-            let board = tictactoe.board();
-            for (row, row_tiles) in board.iter().enumerate() {
-                for (col, &tile) in row_tiles.iter().enumerate() {
-                    match tile {
-                        Tile::X => {
-                            for line in geometry::generate_x(&layout, row, col) {
-                                line_renderer.draw_line(line.from, line.to, line.thickness);
-                            }
-                        }
-                        Tile::O => {
-                            for line in geometry::generate_o(&layout, row, col) {
-                                line_renderer.draw_line(line.from, line.to, line.thickness);
-                            }
-                        }
-                        Tile::Empty => {}
-                    }
-                }
+            for line in board_lines {
+                line_renderer.draw_colored_line(line);
             }
 
             // Generate score numbers at top (with more padding)
-            let score_y = layout.center_y - (layout.cell_size * 1.5) - 100.0;
-            let x_score = tictactoe.wins(Player::X);
-            let o_score = tictactoe.wins(Player::O);
+            let score_y = layout.center_y - layout.board_size()[1] / 2.0 - 100.0;
+            let [x_score, o_score] = scores;
 
             // X score on left
             for line in geometry::generate_number(
@@ -225,13 +235,14 @@ impl SimRenderer {
             && let Some(leaf_sim) = leaf_sim
         {
             for leaf in leaf_sim.leaves() {
-                // Transform leaf position from world space to screen space
-                let screen_position = layout.world_to_screen(leaf.position);
+                // Blend between ticks, then transform from world space to screen space
+                let screen_position =
+                    layout.world_to_screen(leaf.interpolated_position(self.interpolation_alpha));
 
                 // Scale size and alpha by growth (0.0 = invisible, 1.0 = full)
                 let current_size_x = leaf.size * leaf.growth;
                 let current_size_y = current_size_x * leaf.aspect;
-                let current_alpha = leaf.growth; // Fully opaque when grown
+                let current_alpha = leaf.opacity(); // Fully opaque when grown, fades as it falls
 
                 // Calculate focus point for rotation (makes leaves appear to hang from a point)
                 // For an ellipse, focus is at distance c = sqrt(a² - b²) from center
@@ -243,9 +254,10 @@ impl SimRenderer {
                     0.0 // Degenerate case (circle)
                 };
 
-                // Focus offset rotated by leaf rotation angle
-                let focus_offset_x = focus_distance * leaf.rotation.cos();
-                let focus_offset_y = focus_distance * leaf.rotation.sin();
+                // Focus offset rotated by leaf rotation angle, swayed by the wind
+                let rotation = leaf.interpolated_rotation(self.interpolation_alpha);
+                let focus_offset_x = focus_distance * rotation.cos();
+                let focus_offset_y = focus_distance * rotation.sin();
 
                 // Adjust center so rotation happens around focus point instead of center
                 let adjusted_center = [
@@ -257,8 +269,8 @@ impl SimRenderer {
                     center: adjusted_center,
                     radius_x: current_size_x,
                     radius_y: current_size_y,
-                    rotation: leaf.rotation,
-                    color: LEAF_COLORS[leaf.color_variant as usize % 4],
+                    rotation,
+                    color: wilted_color(LEAF_COLORS[leaf.color_variant as usize % 4], leaf.wilt),
                     alpha: current_alpha,
                 });
             }
@@ -297,4 +309,118 @@ impl SimRenderer {
         // End frame for shaders
         self.shader_registry.end_frame();
     }
+
+    /// Returns lines tinting each empty cell by what playing there leads to, with
+    /// the number of moves left in the corner
+    ///
+    /// Positions too large to analyze get no hints.
+    fn hint_lines(
+        &mut self,
+        layout: &geometry::BoardLayout,
+        game: &TicTacToeSimulation,
+    ) -> Vec<Line> {
+        let key = (game.board().clone(), game.current_player(), game.variant());
+        let cached = self
+            .hint_cache
+            .as_ref()
+            .is_some_and(|(cached, _)| *cached == key);
+        if !cached {
+            self.hint_cache = game.analyze().map(|analysis| (key, analysis));
+        }
+        let Some((_, analysis)) = &self.hint_cache else {
+            return Vec::new();
+        };
+
+        let mut lines = Vec::new();
+        for cell in analysis {
+            let color = match cell.verdict {
+                Verdict::Win => HINT_WIN_COLOR,
+                Verdict::Draw => HINT_DRAW_COLOR,
+                Verdict::Loss => HINT_LOSS_COLOR,
+            };
+            lines.push(geometry::generate_cell_fill(layout, cell.row, cell.col).with_color(color));
+
+            let center = layout.cell_center(cell.row, cell.col);
+            let corner = layout.cell_size * 0.4;
+            lines.extend(geometry::generate_number(
+                cell.distance,
+                center[0] - corner,
+                center[1] - corner,
+                layout.cell_size * 0.08,
+                layout.cell_size * 0.14,
+                layout.cell_size * 0.04,
+                2.0,
+            ));
+        }
+        lines
+    }
+}
+
+/// Blends a leaf's green towards brown as it wilts (0.0-1.0)
+fn wilted_color(color: [f32; 3], wilt: f32) -> [f32; 3] {
+    std::array::from_fn(|i| color[i] + (WILTED_LEAF_COLOR[i] - color[i]) * wilt)
+}
+
+/// Returns the lines for a piece at the given cell, if any
+fn piece_lines(layout: &geometry::BoardLayout, tile: Tile, row: usize, col: usize) -> Vec<Line> {
+    match tile {
+        Tile::X => geometry::generate_x(layout, row, col),
+        Tile::O => geometry::generate_o(layout, row, col),
+        Tile::Empty => Vec::new(),
+    }
+}
+
+/// Returns the lines for each player's remaining whole seconds below the board,
+/// X on the left and O on the right, with the running clock drawn bolder
+fn clock_lines(layout: &geometry::BoardLayout, clock: &Clock, to_move: Player) -> Vec<Line> {
+    let clock_y = layout.center_y + layout.board_size()[1] / 2.0 + 50.0;
+    let mut lines = Vec::new();
+    for (player, x) in [
+        (Player::X, layout.center_x - 80.0),
+        (Player::O, layout.center_x + 50.0),
+    ] {
+        let thickness = if player == to_move {
+            layout.line_thickness
+        } else {
+            layout.line_thickness * 0.5
+        };
+        lines.extend(geometry::generate_number(
+            clock.remaining(player).ceil() as u32,
+            x,
+            clock_y,
+            20.0,
+            34.0,
+            8.0,
+            thickness,
+        ));
+    }
+    lines
+}
+
+/// Returns the lines for an ultimate tic-tac-toe grid: the nested grid, every
+/// piece, a large piece over each won sub-board and an outline around the
+/// sub-board the next move must be played in
+fn ultimate_lines(layout: &geometry::BoardLayout, game: &UltimateTicTacToeSimulation) -> Vec<Line> {
+    let mut lines = geometry::generate_nested_grid(layout, ultimate::BLOCK);
+    for row in 0..ultimate::SIZE {
+        for col in 0..ultimate::SIZE {
+            lines.extend(piece_lines(layout, game.tile(row, col), row, col));
+        }
+    }
+
+    let blocks = layout.blocks(ultimate::BLOCK);
+    for row in 0..ultimate::BLOCK {
+        for col in 0..ultimate::BLOCK {
+            if let GameState::Won(player) = game.board_state(row, col) {
+                lines.extend(piece_lines(&blocks, player.to_tile(), row, col));
+            }
+        }
+    }
+
+    if game.game_state() == GameState::Playing
+        && let Some((row, col)) = game.target()
+    {
+        lines.extend(geometry::generate_outline(&blocks, row, col));
+    }
+    lines
 }
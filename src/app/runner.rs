@@ -1,7 +1,8 @@
 //! Main application handler for the game
 
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use tracing::{error, info, warn};
 use winit::application::ApplicationHandler;
@@ -9,10 +10,9 @@ use winit::event::WindowEvent;
 use winit::event_loop::ActiveEventLoop;
 use winit::window::{Window, WindowId};
 
-#[cfg(debug_assertions)]
 use winit::keyboard::{KeyCode, PhysicalKey};
 
-use super::debug_ui::{DebugUIState, MouseDebugInfo};
+use super::debug_ui::{DebugCommand, DebugUIState, MouseDebugInfo};
 use super::input::{
     GameAction, GameInputHandler, InputCollector, InputContext, MouseButton as InputMouseButton,
     Rect, ViewportId,
@@ -20,7 +20,11 @@ use super::input::{
 use super::renderer::Renderer;
 use super::window::window_attributes_from_config;
 use super::{config::AppConfig, geometry};
-use crate::sim::World;
+use crate::sim::rng::derive_seed;
+use crate::sim::tictactoe::ai::TicTacToeAi;
+use crate::sim::tictactoe::{GameState, Tile};
+use crate::sim::ultimate;
+use crate::sim::{Replay, RewindConfig, SimulationRegistry, World, WorldAction};
 
 /// Main game application
 pub struct App {
@@ -35,6 +39,14 @@ pub struct App {
     input_context: InputContext,
     // Debug info
     last_click_info: Option<String>,
+    /// Where to save the replay when recording stops (defaults to ./replays)
+    replay_path: Option<PathBuf>,
+    /// Computer opponent, if enabled in the config
+    ai: Option<TicTacToeAi>,
+    /// Seconds left before the computer makes its move
+    ai_timer: f32,
+    /// Whether the board is tinted with perfect-play hints
+    show_hints: bool,
 }
 
 impl App {
@@ -50,16 +62,57 @@ impl App {
         let game_handler = Box::new(GameInputHandler::new(ViewportId(0)));
         input_context.register_handler(game_handler);
 
+        let mut world = SimulationRegistry::with_builtins()
+            .build_world(&config.sim)
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Invalid [sim] config, using default simulations");
+                World::new()
+            });
+        world.init_board_vines();
+        info!(seed = world.rng_seed(), "World seeded");
+
+        // Keep recent history around for stepping backwards while debugging
+        if cfg!(debug_assertions) {
+            world.enable_rewind(RewindConfig::default());
+        }
+
+        // The computer only plays standard boards; with ultimate on screen it would
+        // be moving on a board nobody can see
+        let ai_enabled = config.ai.enabled && world.ultimate().is_none();
+        if config.ai.enabled && !ai_enabled {
+            warn!("Computer opponent does not play ultimate tic-tac-toe, disabling it");
+        }
+        let ai =
+            ai_enabled.then(|| TicTacToeAi::new(&config.ai, derive_seed(world.rng_seed(), "ai")));
+        let ai_timer = config.ai.move_delay;
+
         Self {
             config,
             window: None,
             renderer: None,
-            world: World::new(),
+            world,
             debug_ui: DebugUIState::default(),
             last_update: None,
             input_collector: InputCollector::new(),
             input_context,
             last_click_info: None,
+            replay_path: None,
+            ai,
+            ai_timer,
+            show_hints: false,
+        }
+    }
+
+    /// Starts recording a replay that is saved to `path` when recording stops or the app exits
+    pub fn record_to(&mut self, path: impl Into<PathBuf>) {
+        self.replay_path = Some(path.into());
+        self.start_recording();
+    }
+
+    /// Plays back a recorded replay instead of live input
+    pub fn play_replay(&mut self, replay: Replay) {
+        if let Err(e) = self.world.start_playback(replay) {
+            error!(error = %e, "Failed to start replay playback");
         }
     }
 
@@ -80,6 +133,53 @@ impl Default for App {
 }
 
 impl App {
+    fn start_recording(&mut self) {
+        if let Err(e) = self.world.start_recording() {
+            error!(error = %e, "Failed to start replay recording");
+        }
+    }
+
+    /// Stops recording (if active) and writes the replay to disk
+    fn stop_recording(&mut self) {
+        let Some(replay) = self.world.stop_recording() else {
+            return;
+        };
+
+        let path = self.replay_path.clone().unwrap_or_else(default_replay_path);
+        if let Some(dir) = path.parent()
+            && let Err(e) = std::fs::create_dir_all(dir)
+        {
+            error!(error = %e, path = %dir.display(), "Failed to create replay directory");
+            return;
+        }
+
+        match replay.save_to(&path) {
+            Ok(()) => info!(path = %path.display(), "Replay saved"),
+            Err(e) => error!(error = %e, path = %path.display(), "Failed to save replay"),
+        }
+    }
+
+    /// Applies commands requested from the debug UI this frame
+    fn process_debug_commands(&mut self) {
+        for command in self.debug_ui.take_commands() {
+            match command {
+                DebugCommand::World(action) => {
+                    self.world.apply(action);
+                }
+                DebugCommand::StartRecording => self.start_recording(),
+                DebugCommand::StopRecording => self.stop_recording(),
+                DebugCommand::StepBack => {
+                    self.world.step_back();
+                }
+                DebugCommand::RewindTo(tick) => {
+                    self.world.rewind_to(tick);
+                }
+                DebugCommand::Undo => self.undo_move(),
+                DebugCommand::Redo => self.redo_move(),
+            }
+        }
+    }
+
     /// Toggles debug window (debug builds only)
     #[cfg(debug_assertions)]
     fn toggle_debug_window(&mut self) {
@@ -115,25 +215,50 @@ impl App {
 
         for action in actions {
             if let GameAction::ViewportClick {
-                local_pos,
-                button: InputMouseButton::Left,
-                ..
+                local_pos, button, ..
             } = action
+                && matches!(button, InputMouseButton::Left | InputMouseButton::Right)
             {
-                self.process_viewport_click(local_pos, &config, viewport_rect);
+                let alternate = button == InputMouseButton::Right;
+                self.process_viewport_click(local_pos, alternate, &config, viewport_rect);
             }
         }
     }
 
     /// Process a click in the game viewport
+    ///
+    /// `alternate` clicks (right button) place the player's other piece in variants
+    /// that allow a choice, and are ignored otherwise.
     fn process_viewport_click(
         &mut self,
         local_pos: [f32; 2],
+        alternate: bool,
         config: &wgpu::SurfaceConfiguration,
         viewport_rect: Option<Rect>,
     ) {
+        let tile = if alternate {
+            let Some(tile) = self.alternate_piece() else {
+                return;
+            };
+            Some(tile)
+        } else {
+            None
+        };
+
         // Create board layout matching the one used in rendering
-        let layout = geometry::BoardLayout::centered(config.width as f32, config.height as f32);
+        let (columns, rows) = if self.world.ultimate().is_some() {
+            (ultimate::SIZE, ultimate::SIZE)
+        } else if let Some(board) = self.world.tictactoe().map(|t| t.board()) {
+            (board.width(), board.height())
+        } else {
+            return;
+        };
+        let layout = geometry::BoardLayout::centered(
+            config.width as f32,
+            config.height as f32,
+            columns,
+            rows,
+        );
 
         // Scale viewport coordinates to texture coordinates
         // The texture is rendered at config dimensions but displayed scaled in viewport
@@ -145,28 +270,28 @@ impl App {
             local_pos // Fallback if no viewport info
         };
 
+        // A finished game stays on the board until its reset delay runs out; any
+        // click on the board starts the next one sooner
+        if self
+            .game_state()
+            .is_some_and(|state| state != GameState::Playing)
+        {
+            if self.world.apply(WorldAction::ResetBoard) {
+                self.last_click_info = Some("New game".to_string());
+            }
+            return;
+        }
+
         // Convert texture coordinates to board cell
         if let Some((row, col)) = layout.screen_to_cell(texture_pos[0], texture_pos[1]) {
-            // Try to make the move
-            if let Some(tictactoe) = self.world.tictactoe_mut()
-                && tictactoe.make_move(row, col)
-            {
+            if self.is_ai_turn() {
+                self.last_click_info = Some("Waiting for the computer's move".to_string());
+            } else if self.make_move(row, col, tile) {
                 self.last_click_info = Some(format!("Placed piece at ({}, {})", row, col));
-                info!("Placed piece at ({}, {})", row, col);
-
-                // Check if game is over and reset
-                use crate::sim::tictactoe::GameState;
-                match tictactoe.game_state() {
-                    GameState::Won(player) => {
-                        info!("Player {:?} won!", player);
-                        tictactoe.reset();
-                    }
-                    GameState::Draw => {
-                        info!("Game is a draw!");
-                        tictactoe.reset();
-                    }
-                    GameState::Playing => {}
-                }
+            } else if self.world.is_replaying() {
+                self.last_click_info = Some("Replay playing, input ignored".to_string());
+            } else if self.world.ultimate().is_some() {
+                self.last_click_info = Some(format!("Cell ({}, {}) is not playable", row, col));
             } else {
                 self.last_click_info = Some(format!("Cell ({}, {}) already occupied", row, col));
             }
@@ -177,6 +302,93 @@ impl App {
             ));
         }
     }
+
+    /// Places a piece for the current player, resetting the board if the game ends
+    ///
+    /// `tile` picks the piece in variants that allow a choice; None places the
+    /// player's usual piece.
+    fn make_move(&mut self, row: usize, col: usize, tile: Option<Tile>) -> bool {
+        let action = if self.world.ultimate().is_some() {
+            WorldAction::MakeUltimateMove { row, col }
+        } else if let Some(tile) = tile {
+            WorldAction::PlacePiece { row, col, tile }
+        } else {
+            WorldAction::MakeMove { row, col }
+        };
+        if !self.world.apply(action) {
+            return false;
+        }
+        info!("Placed piece at ({}, {})", row, col);
+
+        match self.game_state() {
+            Some(GameState::Won(player)) => info!("Player {:?} won!", player),
+            Some(GameState::Draw) => info!("Game is a draw!"),
+            Some(GameState::Playing | GameState::WonOnTime(_)) | None => {}
+        }
+        true
+    }
+
+    /// Returns the state of the game being played, preferring ultimate tic-tac-toe
+    fn game_state(&self) -> Option<GameState> {
+        match self.world.ultimate() {
+            Some(ultimate) => Some(ultimate.game_state()),
+            None => self.world.tictactoe().map(|t| t.game_state()),
+        }
+    }
+
+    /// Takes back the last move, plus the computer's reply so the human is to move
+    fn undo_move(&mut self) {
+        if self.world.apply(WorldAction::UndoMove) && self.is_ai_turn() {
+            self.world.apply(WorldAction::UndoMove);
+        }
+        self.ai_timer = self.config.ai.move_delay;
+    }
+
+    /// Replays the last undone move, plus the computer's reply if it was undone too
+    fn redo_move(&mut self) {
+        if self.world.apply(WorldAction::RedoMove) && self.is_ai_turn() {
+            self.world.apply(WorldAction::RedoMove);
+        }
+        self.ai_timer = self.config.ai.move_delay;
+    }
+
+    /// Returns the second piece the player to move may place, if the variant offers one
+    fn alternate_piece(&self) -> Option<Tile> {
+        if self.world.ultimate().is_some() {
+            return None;
+        }
+        let game = self.world.tictactoe()?;
+        game.rules().pieces(game.current_player()).get(1).copied()
+    }
+
+    /// Returns true if the computer controls the player to move
+    fn is_ai_turn(&self) -> bool {
+        match (&self.ai, self.world.tictactoe()) {
+            (Some(ai), Some(game)) => ai.is_turn(game),
+            _ => false,
+        }
+    }
+
+    /// Counts down the computer's move delay and plays its move once it elapses
+    fn update_ai(&mut self, delta_time: f32) {
+        if !self.is_ai_turn() || self.world.is_paused() || self.world.is_replaying() {
+            self.ai_timer = self.config.ai.move_delay;
+            return;
+        }
+
+        self.ai_timer -= delta_time;
+        if self.ai_timer > 0.0 {
+            return;
+        }
+        self.ai_timer = self.config.ai.move_delay;
+
+        let (Some(ai), Some(game)) = (&mut self.ai, self.world.tictactoe()) else {
+            return;
+        };
+        if let Some((row, col, tile)) = ai.choose_move(game) {
+            self.make_move(row, col, Some(tile));
+        }
+    }
 }
 
 impl ApplicationHandler for App {
@@ -238,9 +450,13 @@ impl ApplicationHandler for App {
 
             // Apply game actions to simulations
             self.process_game_actions();
+            self.update_ai(delta_time);
 
-            // Update simulation
-            self.world.tick(delta_time);
+            // Update simulation in fixed-size ticks, drawing partway to the next one
+            let alpha = self.world.advance(delta_time);
+            if let Some(renderer) = &mut self.renderer {
+                renderer.set_interpolation_alpha(alpha);
+            }
 
             // Request redraw after simulation update
             if let Some(window) = &self.window {
@@ -266,8 +482,26 @@ impl ApplicationHandler for App {
         }
 
         // Let egui handle the event for UI interactions
-        if let (Some(renderer), Some(window)) = (&mut self.renderer, &self.window) {
-            let _ = renderer.handle_event(window, &event);
+        let egui_consumed = match (&mut self.renderer, &self.window) {
+            (Some(renderer), Some(window)) => renderer.handle_event(window, &event),
+            _ => false,
+        };
+
+        // Undo/redo hotkeys: Ctrl+Z, Ctrl+Y or Ctrl+Shift+Z
+        if !egui_consumed
+            && let WindowEvent::KeyboardInput { event, .. } = &event
+            && event.state.is_pressed()
+            && !event.repeat
+        {
+            let modifiers = self.input_collector.state().keyboard.modifiers;
+            if modifiers.ctrl || modifiers.meta {
+                match event.physical_key {
+                    PhysicalKey::Code(KeyCode::KeyZ) if modifiers.shift => self.redo_move(),
+                    PhysicalKey::Code(KeyCode::KeyZ) => self.undo_move(),
+                    PhysicalKey::Code(KeyCode::KeyY) => self.redo_move(),
+                    _ => {}
+                }
+            }
         }
 
         // Handle debug hotkeys (debug builds only)
@@ -283,6 +517,7 @@ impl ApplicationHandler for App {
         match event {
             WindowEvent::CloseRequested => {
                 info!("Close requested, exiting");
+                self.stop_recording();
                 event_loop.exit();
             }
             WindowEvent::Resized(new_size) => {
@@ -292,14 +527,13 @@ impl ApplicationHandler for App {
             }
             WindowEvent::RedrawRequested => {
                 if let (Some(renderer), Some(window)) = (&mut self.renderer, &self.window) {
-                    // Initialize leaf vines if needed (mutable borrow of world)
-                    renderer.init_leaf_vines(&mut self.world);
-
                     let debug_ui = &mut self.debug_ui;
                     let world = &self.world;
                     let config = renderer.config().clone();
                     let input_context = &mut self.input_context;
                     let last_click_info = &self.last_click_info;
+                    let show_hints = &mut self.show_hints;
+                    renderer.set_show_hints(*show_hints);
 
                     // Viewports are re-registered each frame (no need to clear)
                     // We need them to persist between frames for input processing
@@ -313,6 +547,30 @@ impl ApplicationHandler for App {
                                 ui.heading("Tic-Tac-Toe");
                                 ui.separator();
                                 ui.label("Click the board to play!");
+                                ui.label("Ctrl+Z to undo, Ctrl+Y to redo");
+
+                                if let Some(tictactoe) = world.tictactoe() {
+                                    ui.checkbox(show_hints, "Show hints").on_hover_text(
+                                        "Green cells win, yellow draw and red lose with \
+                                             perfect play; the number counts moves left",
+                                    );
+                                    ui.separator();
+                                    ui.label("Moves:");
+                                    egui::ScrollArea::vertical().show(ui, |ui| {
+                                        for (index, record) in
+                                            tictactoe.history().iter().enumerate()
+                                        {
+                                            ui.label(format!(
+                                                "{}. {:?} ({}, {}) @{}",
+                                                index + 1,
+                                                record.player,
+                                                record.row,
+                                                record.col,
+                                                record.tick
+                                            ));
+                                        }
+                                    });
+                                }
                             });
 
                         egui::CentralPanel::default().show(ctx, |ui| {
@@ -371,8 +629,19 @@ impl ApplicationHandler for App {
                         }
                     }
                 }
+
+                self.process_debug_commands();
             }
             _ => {}
         }
     }
 }
+
+/// Returns a timestamped replay path under ./replays
+fn default_replay_path() -> PathBuf {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    PathBuf::from("replays").join(format!("replay-{}.replay", secs))
+}
@@ -0,0 +1,500 @@
+//! Self-play arena for tic-tac-toe strategies
+//!
+//! Pits computer strategies against each other over many headless games and
+//! tallies the results. Every pair of strategies plays the same number of games,
+//! alternating who moves first, and each game's randomness is derived from the
+//! arena seed so a run can be reproduced exactly. Used by `game arena`.
+//!
+//! # Example
+//!
+//! ```
+//! use oil_pool::arena::{self, ArenaOptions, Strategy};
+//!
+//! let options = ArenaOptions {
+//!     strategies: vec![Strategy::Random, Strategy::Greedy],
+//!     games: 20,
+//!     seed: 7,
+//!     ..Default::default()
+//! };
+//! let report = arena::run(&options).unwrap();
+//! assert_eq!(report.matchups[0].games, 20);
+//! ```
+
+use std::fmt;
+
+use serde::Serialize;
+use tabled::{
+    builder::Builder,
+    settings::{Alignment, Modify, Style, object::Rows},
+};
+use tracing::{info, warn};
+
+use crate::headless::OutputFormat;
+use crate::sim::rng::derive_seed;
+use crate::sim::tictactoe::ai::{AiConfig, Difficulty, TicTacToeAi};
+use crate::sim::tictactoe::{BoardConfig, GameState, Player, TicTacToeSimulation, Variant};
+
+/// z-score of a two-sided 95% confidence interval
+const Z_95: f64 = 1.959_964;
+
+/// A computer strategy that can enter the arena
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Strategy {
+    /// Uniformly random legal moves
+    Random,
+    /// Takes immediate wins, blocks immediate losses, otherwise random
+    Greedy,
+    /// Minimax with occasional random blunders
+    Easy,
+    /// Full-strength minimax
+    Minimax,
+}
+
+impl Strategy {
+    /// Every strategy, weakest first
+    pub const ALL: [Strategy; 4] = [
+        Strategy::Random,
+        Strategy::Greedy,
+        Strategy::Easy,
+        Strategy::Minimax,
+    ];
+
+    /// Returns the computer difficulty that plays this strategy
+    pub fn difficulty(self) -> Difficulty {
+        match self {
+            Strategy::Random => Difficulty::Random,
+            Strategy::Greedy => Difficulty::Greedy,
+            Strategy::Easy => Difficulty::Easy,
+            Strategy::Minimax => Difficulty::Perfect,
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Strategy::Random => "random",
+            Strategy::Greedy => "greedy",
+            Strategy::Easy => "easy",
+            Strategy::Minimax => "minimax",
+        })
+    }
+}
+
+/// Arena settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArenaOptions {
+    /// Strategies to enter; each distinct pair plays a matchup
+    pub strategies: Vec<Strategy>,
+    /// Games per matchup
+    pub games: u32,
+    /// Seed all game randomness is derived from
+    pub seed: u64,
+    pub board: BoardConfig,
+    pub variant: Variant,
+    /// Moves minimax searches ahead
+    pub max_depth: u32,
+}
+
+impl Default for ArenaOptions {
+    fn default() -> Self {
+        Self {
+            strategies: Strategy::ALL.to_vec(),
+            games: 1000,
+            seed: 0,
+            board: BoardConfig::default(),
+            variant: Variant::default(),
+            max_depth: AiConfig::default().max_depth,
+        }
+    }
+}
+
+/// Errors that can occur while setting up an arena run
+#[derive(Debug, thiserror::Error)]
+pub enum ArenaError {
+    #[error("the arena needs at least two different strategies")]
+    TooFewStrategies,
+    #[error("each matchup must play at least one game")]
+    NoGames,
+}
+
+/// How often an outcome happened, with a 95% Wilson score interval on its rate
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Tally {
+    pub count: u32,
+    pub rate: f64,
+    pub low: f64,
+    pub high: f64,
+}
+
+impl Tally {
+    /// Tallies `count` occurrences out of `games`
+    pub fn new(count: u32, games: u32) -> Self {
+        let (low, high) = wilson_interval(count, games);
+        Self {
+            count,
+            rate: if games == 0 {
+                0.0
+            } else {
+                count as f64 / games as f64
+            },
+            low,
+            high,
+        }
+    }
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({:.1}% [{:.1}, {:.1}])",
+            self.count,
+            self.rate * 100.0,
+            self.low * 100.0,
+            self.high * 100.0
+        )
+    }
+}
+
+/// Results of one pair of strategies, from the first one's point of view
+///
+/// Rates are out of the games that finished; aborted games are left out of every
+/// tally.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MatchResult {
+    pub first: Strategy,
+    pub second: Strategy,
+    pub games: u32,
+    /// Games stopped because a strategy had no move or made an illegal one
+    pub aborted: u32,
+    /// Games `first` won
+    pub wins: Tally,
+    pub draws: Tally,
+    /// Games `second` won
+    pub losses: Tally,
+    /// Games `first` won while moving first
+    pub wins_as_x: u32,
+    /// Games `first` won while moving second
+    pub wins_as_o: u32,
+}
+
+/// Combined results of one strategy against every opponent
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Standing {
+    pub strategy: Strategy,
+    /// Finished games played
+    pub games: u32,
+    pub wins: Tally,
+    pub draws: Tally,
+    pub losses: Tally,
+}
+
+/// Result of an arena run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArenaReport {
+    pub seed: u64,
+    pub variant: Variant,
+    pub board: BoardConfig,
+    pub games_per_matchup: u32,
+    pub matchups: Vec<MatchResult>,
+    /// Strategies ordered by points (a win is 1, a draw ½), best first
+    pub standings: Vec<Standing>,
+}
+
+impl ArenaReport {
+    /// Formats the report in the requested format
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Text => self.to_string(),
+            OutputFormat::Json => {
+                serde_json::to_string_pretty(self).expect("report is always serializable")
+            }
+        }
+    }
+}
+
+impl fmt::Display for ArenaReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} games per matchup on {}x{} ({} in a row, {} rules), seed {}",
+            self.games_per_matchup,
+            self.board.width,
+            self.board.height,
+            self.board.win_length,
+            self.variant,
+            self.seed
+        )?;
+        writeln!(f, "Counts with rate [95% confidence interval]\n")?;
+
+        let mut builder = Builder::default();
+        builder.push_record([
+            "Strategy",
+            "Opponent",
+            "Wins",
+            "Wins as X / O",
+            "Draws",
+            "Losses",
+        ]);
+        for matchup in &self.matchups {
+            builder.push_record([
+                matchup.first.to_string(),
+                matchup.second.to_string(),
+                matchup.wins.to_string(),
+                format!("{} / {}", matchup.wins_as_x, matchup.wins_as_o),
+                matchup.draws.to_string(),
+                matchup.losses.to_string(),
+            ]);
+        }
+        writeln!(f, "{}\n", table(builder))?;
+        for matchup in self.matchups.iter().filter(|m| m.aborted > 0) {
+            writeln!(
+                f,
+                "{} vs {}: {} games aborted by an illegal or missing move\n",
+                matchup.first, matchup.second, matchup.aborted
+            )?;
+        }
+
+        let mut builder = Builder::default();
+        builder.push_record(["Strategy", "Games", "Wins", "Draws", "Losses"]);
+        for standing in &self.standings {
+            builder.push_record([
+                standing.strategy.to_string(),
+                standing.games.to_string(),
+                standing.wins.to_string(),
+                standing.draws.to_string(),
+                standing.losses.to_string(),
+            ]);
+        }
+        write!(f, "{}", table(builder))
+    }
+}
+
+/// Finishes a table in the health reporter's style
+fn table(builder: Builder) -> tabled::Table {
+    let mut table = builder.build();
+    table
+        .with(Style::rounded())
+        .with(Modify::new(Rows::first()).with(Alignment::center()));
+    table
+}
+
+/// Returns the 95% Wilson score interval for a rate of `successes` in `trials`
+///
+/// Unlike the normal approximation it stays within 0-1 and behaves at rates near
+/// 0 or 1, which are common here: minimax never loses.
+pub fn wilson_interval(successes: u32, trials: u32) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let z2 = Z_95 * Z_95;
+    let denominator = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denominator;
+    let margin = Z_95 * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+/// Plays every matchup described by `options`
+pub fn run(options: &ArenaOptions) -> Result<ArenaReport, ArenaError> {
+    let mut strategies = Vec::new();
+    for &strategy in &options.strategies {
+        if !strategies.contains(&strategy) {
+            strategies.push(strategy);
+        }
+    }
+    if strategies.len() < 2 {
+        return Err(ArenaError::TooFewStrategies);
+    }
+    if options.games == 0 {
+        return Err(ArenaError::NoGames);
+    }
+
+    let mut matchups = Vec::new();
+    for (i, &first) in strategies.iter().enumerate() {
+        for &second in &strategies[i + 1..] {
+            info!(%first, %second, games = options.games, "Playing matchup");
+            matchups.push(play_matchup(options, first, second));
+        }
+    }
+
+    let mut standings: Vec<_> = strategies
+        .iter()
+        .map(|&strategy| standing(strategy, &matchups))
+        .collect();
+    let points = |s: &Standing| 2 * s.wins.count + s.draws.count;
+    standings.sort_by_key(|s| std::cmp::Reverse(points(s)));
+
+    Ok(ArenaReport {
+        seed: options.seed,
+        variant: options.variant,
+        board: options.board.clamped(),
+        games_per_matchup: options.games,
+        matchups,
+        standings,
+    })
+}
+
+/// Plays `options.games` games between two strategies, `first` moving first in the
+/// even-numbered ones
+fn play_matchup(options: &ArenaOptions, first: Strategy, second: Strategy) -> MatchResult {
+    let mut counts = Counts::default();
+    for game in 0..options.games {
+        let first_player = if game.is_multiple_of(2) {
+            Player::X
+        } else {
+            Player::O
+        };
+        let stream = format!("arena/{first}/{second}/{game}");
+        let mut first_ai = contestant(options, first, first_player, &stream);
+        let mut second_ai = contestant(options, second, first_player.opponent(), &stream);
+
+        let result = play_game(options, &mut first_ai, &mut second_ai);
+        if result.is_none() {
+            warn!(%first, %second, game, "Game aborted by an illegal or missing move");
+        }
+        counts.record(first_player, result);
+    }
+
+    let finished = options.games - counts.aborted;
+    MatchResult {
+        first,
+        second,
+        games: options.games,
+        aborted: counts.aborted,
+        wins: Tally::new(counts.wins, finished),
+        draws: Tally::new(counts.draws, finished),
+        losses: Tally::new(counts.losses, finished),
+        wins_as_x: counts.wins_as_x,
+        wins_as_o: counts.wins_as_o,
+    }
+}
+
+/// Running totals for one matchup, from the first strategy's point of view
+#[derive(Debug, Default)]
+struct Counts {
+    wins: u32,
+    draws: u32,
+    losses: u32,
+    aborted: u32,
+    wins_as_x: u32,
+    wins_as_o: u32,
+}
+
+impl Counts {
+    /// Adds a game the first strategy played as `first_player`; None is an
+    /// aborted game
+    fn record(&mut self, first_player: Player, result: Option<GameState>) {
+        match result {
+            Some(GameState::Won(winner) | GameState::WonOnTime(winner))
+                if winner == first_player =>
+            {
+                self.wins += 1;
+                match first_player {
+                    Player::X => self.wins_as_x += 1,
+                    Player::O => self.wins_as_o += 1,
+                }
+            }
+            Some(GameState::Won(_) | GameState::WonOnTime(_)) => self.losses += 1,
+            Some(GameState::Draw) => self.draws += 1,
+            Some(GameState::Playing) | None => self.aborted += 1,
+        }
+    }
+}
+
+/// Creates the computer player for `strategy`, seeded from the game's stream
+fn contestant(
+    options: &ArenaOptions,
+    strategy: Strategy,
+    player: Player,
+    stream: &str,
+) -> TicTacToeAi {
+    let config = AiConfig {
+        enabled: true,
+        player,
+        difficulty: strategy.difficulty(),
+        max_depth: options.max_depth,
+        ..Default::default()
+    };
+    let seed = derive_seed(options.seed, &format!("{stream}/{player:?}"));
+    TicTacToeAi::new(&config, seed)
+}
+
+/// Plays one game to the end and returns how it finished, or None if a player had
+/// no move or made an illegal one
+fn play_game(
+    options: &ArenaOptions,
+    a: &mut TicTacToeAi,
+    b: &mut TicTacToeAi,
+) -> Option<GameState> {
+    let mut game =
+        TicTacToeSimulation::with_variant(options.board, options.variant).with_reset_delay(0.0);
+    while game.game_state() == GameState::Playing {
+        let mover = if a.is_turn(&game) { &mut *a } else { &mut *b };
+        let (row, col, tile) = mover.choose_move(&game)?;
+        if !game.place_piece(row, col, tile) {
+            return None;
+        }
+    }
+    Some(game.game_state())
+}
+
+/// Sums `strategy`'s results over every matchup it played
+fn standing(strategy: Strategy, matchups: &[MatchResult]) -> Standing {
+    let (mut games, mut wins, mut draws, mut losses) = (0, 0, 0, 0);
+    for matchup in matchups {
+        let (won, lost) = if matchup.first == strategy {
+            (matchup.wins.count, matchup.losses.count)
+        } else if matchup.second == strategy {
+            (matchup.losses.count, matchup.wins.count)
+        } else {
+            continue;
+        };
+        games += matchup.games - matchup.aborted;
+        wins += won;
+        draws += matchup.draws.count;
+        losses += lost;
+    }
+    Standing {
+        strategy,
+        games,
+        wins: Tally::new(wins, games),
+        draws: Tally::new(draws, games),
+        losses: Tally::new(losses, games),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wilson_interval() {
+        let (low, high) = wilson_interval(50, 100);
+        assert!((low - 0.4038).abs() < 1.0e-3, "{low}");
+        assert!((high - 0.5962).abs() < 1.0e-3, "{high}");
+
+        // Stays inside 0-1 at the extremes, and is wide with little data
+        let (low, high) = wilson_interval(0, 10);
+        assert_eq!(low, 0.0);
+        assert!(high > 0.25 && high < 0.35);
+        assert_eq!(wilson_interval(0, 0), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_aborted_games_are_not_draws() {
+        let mut counts = Counts::default();
+        counts.record(Player::X, Some(GameState::Won(Player::X)));
+        counts.record(Player::O, Some(GameState::Won(Player::O)));
+        counts.record(Player::X, Some(GameState::Won(Player::O)));
+        counts.record(Player::O, Some(GameState::Draw));
+        counts.record(Player::X, None);
+
+        assert_eq!((counts.wins, counts.draws, counts.losses), (2, 1, 1));
+        assert_eq!((counts.wins_as_x, counts.wins_as_o), (1, 1));
+        assert_eq!(counts.aborted, 1);
+    }
+}
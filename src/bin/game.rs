@@ -1,8 +1,15 @@
-use clap::Parser;
-use oil_pool::app::App;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use oil_pool::app::{App, AppConfig};
+use oil_pool::arena::{self, ArenaOptions, Strategy};
 use oil_pool::build_info;
+use oil_pool::headless::{self, HeadlessOptions, OutputFormat, RunLength};
 use oil_pool::health;
+use oil_pool::sim::Replay;
+use oil_pool::sim::tictactoe::{BoardConfig, Variant};
 use sysinfo::System;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 use winit::event_loop::{ControlFlow, EventLoop};
 
@@ -11,13 +18,93 @@ use winit::event_loop::{ControlFlow, EventLoop};
 #[command(name = "game")]
 #[command(about = "Oil Pool Game", long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Run health checks and exit
     #[arg(long)]
     health_check: bool,
 
-    /// Run in headless mode (no window)
+    /// Run the simulation without a window and print a summary
     #[arg(long)]
     headless: bool,
+
+    /// Number of ticks to run in headless mode
+    #[arg(long, requires = "headless", conflicts_with = "seconds")]
+    ticks: Option<u64>,
+
+    /// Seconds of simulation time to run in headless mode (default 10)
+    #[arg(long, requires = "headless")]
+    seconds: Option<f64>,
+
+    /// Fixed delta time per headless tick, in seconds (default: configured timestep)
+    #[arg(long, requires = "headless")]
+    dt: Option<f32>,
+
+    /// World seed (default: configured seed, or random)
+    #[arg(long, requires = "headless")]
+    seed: Option<u64>,
+
+    /// Time scale multiplier (default: configured time scale)
+    #[arg(long, requires = "headless")]
+    time_scale: Option<f32>,
+
+    /// Headless summary format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, requires = "headless")]
+    format: OutputFormat,
+
+    /// Record gameplay actions to a replay file (saved on exit)
+    #[arg(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+
+    /// Play back a recorded replay file
+    #[arg(long, value_name = "PATH", conflicts_with = "record")]
+    replay: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Pit tic-tac-toe strategies against each other and print win/draw/loss tables
+    Arena(ArenaArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct ArenaArgs {
+    /// Strategies to enter; every pair plays a matchup
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = Strategy::ALL)]
+    strategies: Vec<Strategy>,
+
+    /// Games per matchup, alternating who moves first
+    #[arg(long, default_value_t = 1000)]
+    games: u32,
+
+    /// Seed all game randomness is derived from
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Rule set to play
+    #[arg(long, value_enum, default_value_t = Variant::Standard)]
+    variant: Variant,
+
+    /// Board width
+    #[arg(long, default_value_t = 3)]
+    width: usize,
+
+    /// Board height
+    #[arg(long, default_value_t = 3)]
+    height: usize,
+
+    /// Pieces in a row needed to win
+    #[arg(long, default_value_t = 3)]
+    win_length: usize,
+
+    /// Moves minimax searches ahead; lower it on large boards
+    #[arg(long, default_value_t = 9)]
+    depth: u32,
+
+    /// Report format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
 }
 
 fn main() {
@@ -26,8 +113,14 @@ fn main() {
     // Set RUST_LOG environment variable to control log level (e.g., RUST_LOG=debug)
     // Default to debug for app, but info for noisy graphics libraries
     let default_filter = "debug,naga=info,wgpu=info,wgpu_core=info,wgpu_hal=info";
+    // Keep stdout clean for the headless summary
+    let log_writer = if args.headless || args.command.is_some() {
+        BoxMakeWriter::new(std::io::stderr)
+    } else {
+        BoxMakeWriter::new(std::io::stdout)
+    };
     tracing_subscriber::registry()
-        .with(fmt::layer())
+        .with(fmt::layer().with_writer(log_writer))
         .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter)))
         .init();
 
@@ -66,12 +159,15 @@ fn main() {
         std::process::exit(report.exit_code());
     }
 
+    if let Some(Command::Arena(arena_args)) = &args.command {
+        tracing::info!("Running strategy arena");
+        std::process::exit(run_arena(arena_args));
+    }
+
     // Handle headless mode
     if args.headless {
         tracing::info!("Running in headless mode");
-        tracing::warn!("Headless mode not yet fully implemented");
-        // TODO: Implement headless execution (no window, simulation only)
-        std::process::exit(0);
+        std::process::exit(run_headless(&args));
     }
 
     let event_loop = EventLoop::new().expect("Failed to create event loop");
@@ -81,11 +177,83 @@ fn main() {
     // Set APP_PROFILE=release to use release configuration
     let mut app = App::from_env();
 
+    if let Some(path) = &args.replay {
+        match Replay::load_from(path) {
+            Ok(replay) => app.play_replay(replay),
+            Err(e) => {
+                tracing::error!(error = %e, path = %path.display(), "Failed to load replay");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some(path) = args.record {
+        app.record_to(path);
+    }
+
     event_loop
         .run_app(&mut app)
         .expect("Failed to run event loop");
 }
 
+/// Runs the simulation without a window, prints its summary, and returns the exit code
+fn run_headless(args: &Args) -> i32 {
+    let config = AppConfig::load_from_env().unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "Failed to load config, using default configuration");
+        AppConfig::default()
+    });
+
+    let length = match (args.ticks, args.seconds) {
+        (Some(ticks), _) => RunLength::Ticks(ticks),
+        (None, Some(seconds)) => RunLength::Seconds(seconds),
+        (None, None) => HeadlessOptions::default().length,
+    };
+    let options = HeadlessOptions {
+        length,
+        dt: args.dt,
+        seed: args.seed,
+        time_scale: args.time_scale,
+    };
+
+    match headless::run(&config.sim, &options) {
+        Ok(summary) => {
+            println!("{}", summary.render(args.format));
+            0
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Headless run failed");
+            1
+        }
+    }
+}
+
+/// Plays the arena matchups, prints the report, and returns the exit code
+fn run_arena(args: &ArenaArgs) -> i32 {
+    let options = ArenaOptions {
+        strategies: args.strategies.clone(),
+        games: args.games,
+        seed: args.seed,
+        board: BoardConfig {
+            width: args.width,
+            height: args.height,
+            win_length: args.win_length,
+        },
+        variant: args.variant,
+        max_depth: args.depth,
+    };
+
+    match arena::run(&options) {
+        Ok(report) => {
+            println!("{}", report.render(args.format));
+            0
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Arena run failed");
+            1
+        }
+    }
+}
+
 /// Logs anonymous runtime system information
 fn log_system_info() {
     let mut sys = System::new_all();
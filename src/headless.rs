@@ -0,0 +1,195 @@
+//! Headless simulation runner
+//!
+//! Builds a [`World`] from the `[sim]` config, runs it at a fixed delta time without
+//! a window or GPU, and summarizes the result. Used by `game --headless` for CI and
+//! batch experiments.
+//!
+//! # Example
+//!
+//! ```
+//! use oil_pool::headless::{self, HeadlessOptions, RunLength};
+//! use oil_pool::sim::SimConfig;
+//!
+//! let options = HeadlessOptions {
+//!     length: RunLength::Ticks(120),
+//!     seed: Some(7),
+//!     ..Default::default()
+//! };
+//! let summary = headless::run(&SimConfig::default(), &options).unwrap();
+//! assert_eq!(summary.ticks, 120);
+//! ```
+
+use std::fmt;
+
+use serde::Serialize;
+use tracing::info;
+
+use crate::sim::registry::RegistryError;
+use crate::sim::{SimConfig, SimulationRegistry, SimulationStats, World};
+
+/// How long to run the simulation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RunLength {
+    /// A fixed number of ticks
+    Ticks(u64),
+    /// Until at least this much simulation time has elapsed, in seconds
+    Seconds(f64),
+}
+
+/// Headless run settings; unset fields fall back to the `[sim]` config
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessOptions {
+    pub length: RunLength,
+    /// Fixed delta time per tick in seconds (defaults to the configured timestep)
+    pub dt: Option<f32>,
+    /// World seed (defaults to the configured seed, or a random one)
+    pub seed: Option<u64>,
+    /// Time scale multiplier (defaults to the configured time scale)
+    pub time_scale: Option<f32>,
+}
+
+impl Default for HeadlessOptions {
+    fn default() -> Self {
+        Self {
+            length: RunLength::Seconds(10.0),
+            dt: None,
+            seed: None,
+            time_scale: None,
+        }
+    }
+}
+
+/// Errors that can occur while setting up a headless run
+#[derive(Debug, thiserror::Error)]
+pub enum HeadlessError {
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
+    #[error("delta time must be positive, got {0}")]
+    InvalidDt(f32),
+    #[error("time scale must be positive to run for a duration of sim time")]
+    Stalled,
+}
+
+/// Output format for the run summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// Pretty-printed JSON
+    Json,
+}
+
+/// Stats reported by a single simulation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimulationSummary {
+    pub name: String,
+    pub stats: SimulationStats,
+}
+
+/// Result of a headless run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HeadlessSummary {
+    pub seed: u64,
+    pub ticks: u64,
+    pub sim_time: f64,
+    pub dt: f32,
+    pub time_scale: f32,
+    /// World checksum after the final tick, for comparing runs
+    pub checksum: String,
+    pub simulations: Vec<SimulationSummary>,
+}
+
+impl HeadlessSummary {
+    /// Collects the summary of a finished run
+    pub fn from_world(world: &World, dt: f32) -> Self {
+        Self {
+            seed: world.rng_seed(),
+            ticks: world.tick_count(),
+            sim_time: world.sim_time(),
+            dt,
+            time_scale: world.time_scale(),
+            checksum: format!("{:016x}", world.checksum()),
+            simulations: world
+                .simulations()
+                .iter()
+                .map(|sim| SimulationSummary {
+                    name: sim.name().to_string(),
+                    stats: sim.stats(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Formats the summary in the requested format
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Text => self.to_string(),
+            OutputFormat::Json => {
+                serde_json::to_string_pretty(self).expect("summary is always serializable")
+            }
+        }
+    }
+}
+
+impl fmt::Display for HeadlessSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Ran {} ticks ({:.2}s sim time, dt {:.4}s, time scale {})",
+            self.ticks, self.sim_time, self.dt, self.time_scale
+        )?;
+        writeln!(f, "Seed: {}", self.seed)?;
+        write!(f, "Checksum: {}", self.checksum)?;
+        for sim in &self.simulations {
+            write!(f, "\n\n[{}]", sim.name)?;
+            for (key, value) in &sim.stats {
+                match value {
+                    serde_json::Value::String(text) => write!(f, "\n  {key}: {text}")?,
+                    other => write!(f, "\n  {key}: {other}")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds the world described by `config` and runs it according to `options`
+pub fn run(
+    config: &SimConfig,
+    options: &HeadlessOptions,
+) -> Result<HeadlessSummary, HeadlessError> {
+    let config = SimConfig {
+        seed: options.seed.or(config.seed),
+        time_scale: options.time_scale.unwrap_or(config.time_scale),
+        ..config.clone()
+    };
+    let dt = options.dt.unwrap_or(config.timestep.step);
+    if dt.is_nan() || dt <= 0.0 {
+        return Err(HeadlessError::InvalidDt(dt));
+    }
+
+    let mut world = SimulationRegistry::with_builtins().build_world(&config)?;
+    world.init_board_vines();
+
+    let ticks = match options.length {
+        RunLength::Ticks(ticks) => ticks,
+        RunLength::Seconds(seconds) => {
+            let scaled_dt = dt as f64 * world.time_scale() as f64;
+            if scaled_dt <= 0.0 {
+                return Err(HeadlessError::Stalled);
+            }
+            (seconds / scaled_dt).ceil() as u64
+        }
+    };
+
+    info!(
+        seed = world.rng_seed(),
+        ticks, dt, "Running headless simulation"
+    );
+    for _ in 0..ticks {
+        world.tick(dt);
+    }
+
+    Ok(HeadlessSummary::from_world(&world, dt))
+}
@@ -2,6 +2,9 @@
 //!
 //! A game built with Rust, winit, and wgpu.
 
+/// Self-play arena pitting tic-tac-toe strategies against each other
+pub mod arena;
+
 /// Game application - windowing, rendering, and input handling
 pub mod app;
 
@@ -11,5 +14,8 @@ pub mod build_info;
 /// Health check system for validating application startup and status
 pub mod health;
 
+/// Headless simulation runner for CI and batch experiments
+pub mod headless;
+
 /// Game simulation - world state, entities, and physics
 pub mod sim;
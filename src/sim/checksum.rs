@@ -0,0 +1,302 @@
+//! World state checksums
+//!
+//! Hashes simulation state into a single value per tick so two runs, or a replay
+//! and the live session it was recorded from, can be compared to find the first
+//! tick where they diverge. Floats are hashed by their bit patterns and every
+//! integer is written little-endian, so checksums match across runs and platforms.
+
+use std::collections::VecDeque;
+use std::hash::Hasher;
+
+use serde::{Deserialize, Serialize};
+
+use super::rng::{FNV_OFFSET, FNV_PRIME};
+
+/// Stable FNV-1a hasher for simulation state
+#[derive(Debug, Clone)]
+pub struct StateHasher {
+    hash: u64,
+}
+
+impl StateHasher {
+    /// Creates a hasher with the FNV offset basis
+    pub fn new() -> Self {
+        Self { hash: FNV_OFFSET }
+    }
+
+    /// Hashes a float by its bit pattern
+    pub fn write_f32(&mut self, value: f32) {
+        self.write_u32(value.to_bits());
+    }
+
+    /// Hashes a double by its bit pattern
+    pub fn write_f64(&mut self, value: f64) {
+        self.write_u64(value.to_bits());
+    }
+
+    /// Hashes a string, length-prefixed so adjacent strings can't run together
+    pub fn write_str(&mut self, value: &str) {
+        self.write_usize(value.len());
+        self.write(value.as_bytes());
+    }
+}
+
+impl Default for StateHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for StateHasher {
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.hash ^= byte as u64;
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    // Fixed byte order (the default implementations use native endianness)
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, value: u128) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    fn write_i16(&mut self, value: i16) {
+        self.write_u16(value as u16);
+    }
+
+    fn write_i32(&mut self, value: i32) {
+        self.write_u32(value as u32);
+    }
+
+    fn write_i64(&mut self, value: i64) {
+        self.write_u64(value as u64);
+    }
+
+    fn write_i128(&mut self, value: i128) {
+        self.write_u128(value as u128);
+    }
+
+    fn write_isize(&mut self, value: isize) {
+        self.write_u64(value as u64);
+    }
+}
+
+/// Checksum tracking settings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChecksumConfig {
+    /// Record a checksum every `interval` ticks
+    pub interval: u64,
+    /// Log each recorded checksum at debug level
+    pub log: bool,
+    /// Number of recorded checksums to keep
+    pub history: usize,
+}
+
+impl Default for ChecksumConfig {
+    fn default() -> Self {
+        Self {
+            interval: 60,
+            log: false,
+            history: 1024,
+        }
+    }
+}
+
+/// World checksum at a given tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickChecksum {
+    pub tick: u64,
+    pub checksum: u64,
+}
+
+/// Bounded history of recorded checksums, ordered by tick
+#[derive(Debug, Clone)]
+pub struct ChecksumHistory {
+    config: ChecksumConfig,
+    entries: VecDeque<TickChecksum>,
+}
+
+impl ChecksumHistory {
+    /// Creates an empty history
+    pub fn new(config: ChecksumConfig) -> Self {
+        Self {
+            config: ChecksumConfig {
+                interval: config.interval.max(1),
+                ..config
+            },
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// Returns the tracking settings
+    pub fn config(&self) -> ChecksumConfig {
+        self.config
+    }
+
+    /// Returns true if a checksum should be recorded at `tick`
+    pub fn wants(&self, tick: u64) -> bool {
+        tick.is_multiple_of(self.config.interval)
+    }
+
+    /// Records a checksum, discarding any entries at or after its tick
+    pub fn record(&mut self, tick: u64, checksum: u64) {
+        self.truncate_from(tick);
+        self.entries.push_back(TickChecksum { tick, checksum });
+        while self.entries.len() > self.config.history {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Discards entries at or after `tick`
+    pub fn truncate_from(&mut self, tick: u64) {
+        while self.entries.back().is_some_and(|entry| entry.tick >= tick) {
+            self.entries.pop_back();
+        }
+    }
+
+    /// Returns the checksum recorded at `tick`
+    pub fn get(&self, tick: u64) -> Option<u64> {
+        lookup(self.entries.iter().copied(), tick)
+    }
+
+    /// Returns the newest recorded checksum
+    pub fn latest(&self) -> Option<TickChecksum> {
+        self.entries.back().copied()
+    }
+
+    /// Returns every recorded checksum, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = TickChecksum> + '_ {
+        self.entries.iter().copied()
+    }
+
+    /// Returns the first tick recorded in both histories whose checksums differ
+    pub fn first_divergence(&self, other: &[TickChecksum]) -> Option<u64> {
+        first_divergence(self.entries.iter().copied(), other.iter().copied())
+    }
+
+    /// Drops every recorded checksum
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Returns the first tick present in both tick-ordered lists whose checksums differ
+///
+/// Ticks recorded by only one side are skipped, so runs with different checksum
+/// intervals can still be compared.
+pub fn first_divergence(
+    a: impl IntoIterator<Item = TickChecksum>,
+    b: impl IntoIterator<Item = TickChecksum>,
+) -> Option<u64> {
+    let mut a = a.into_iter().peekable();
+    let mut b = b.into_iter().peekable();
+    while let (Some(x), Some(y)) = (a.peek().copied(), b.peek().copied()) {
+        if x.tick < y.tick {
+            a.next();
+        } else if y.tick < x.tick {
+            b.next();
+        } else if x.checksum != y.checksum {
+            return Some(x.tick);
+        } else {
+            a.next();
+            b.next();
+        }
+    }
+    None
+}
+
+/// Finds the checksum recorded at `tick` in a tick-ordered list
+pub(crate) fn lookup(entries: impl IntoIterator<Item = TickChecksum>, tick: u64) -> Option<u64> {
+    entries
+        .into_iter()
+        .find(|entry| entry.tick >= tick)
+        .filter(|entry| entry.tick == tick)
+        .map(|entry| entry.checksum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(pairs: &[(u64, u64)]) -> Vec<TickChecksum> {
+        pairs
+            .iter()
+            .map(|&(tick, checksum)| TickChecksum { tick, checksum })
+            .collect()
+    }
+
+    #[test]
+    fn test_hasher_is_stable() {
+        let mut hasher = StateHasher::new();
+        hasher.write_f32(1.5);
+        hasher.write_str("leaf");
+        let first = hasher.finish();
+
+        let mut again = StateHasher::new();
+        again.write_f32(1.5);
+        again.write_str("leaf");
+        assert_eq!(first, again.finish());
+
+        // Distinct bit patterns hash differently even when they compare equal
+        let mut zero = StateHasher::new();
+        zero.write_f32(0.0);
+        let mut negative_zero = StateHasher::new();
+        negative_zero.write_f32(-0.0);
+        assert_ne!(zero.finish(), negative_zero.finish());
+    }
+
+    #[test]
+    fn test_first_divergence_skips_unshared_ticks() {
+        let a = entries(&[(10, 1), (20, 2), (30, 3), (40, 4)]);
+        let b = entries(&[(20, 2), (25, 9), (30, 7), (40, 8)]);
+        assert_eq!(first_divergence(a.clone(), b), Some(30));
+        assert_eq!(first_divergence(a.clone(), a), None);
+    }
+
+    #[test]
+    fn test_history_is_bounded_and_truncates() {
+        let mut history = ChecksumHistory::new(ChecksumConfig {
+            interval: 10,
+            history: 3,
+            ..Default::default()
+        });
+        for tick in [10, 20, 30, 40] {
+            history.record(tick, tick * 2);
+        }
+        assert_eq!(history.entries().count(), 3);
+        assert_eq!(history.get(10), None);
+        assert_eq!(history.get(20), Some(40));
+
+        // Recording an earlier tick starts a new timeline
+        history.record(30, 0);
+        assert_eq!(
+            history.latest(),
+            Some(TickChecksum {
+                tick: 30,
+                checksum: 0
+            })
+        );
+        assert_eq!(history.get(40), None);
+    }
+}
@@ -0,0 +1,87 @@
+//! Typed event bus for communication between simulations
+//!
+//! During each world tick, after the `tick` phase, every simulation publishes its
+//! queued events in schedule order and then every simulation handles them in
+//! schedule order, before `post_tick`. Events are therefore delivered within the
+//! tick they are published in, in a deterministic order. Events queued outside a
+//! tick (e.g. by player input) are published on the next tick.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// Per-type event queues for the current tick
+#[derive(Default)]
+pub struct EventBus {
+    queues: HashMap<TypeId, Box<dyn Any>>,
+    len: usize,
+}
+
+impl EventBus {
+    /// Creates an empty bus
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues an event for delivery this tick
+    pub fn publish<E: Any>(&mut self, event: E) {
+        self.queues
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Box::new(Vec::<E>::new()))
+            .downcast_mut::<Vec<E>>()
+            .expect("queues are keyed by their event type")
+            .push(event);
+        self.len += 1;
+    }
+
+    /// Returns every event of type `E` published this tick, in publish order
+    pub fn read<E: Any>(&self) -> &[E] {
+        self.queues
+            .get(&TypeId::of::<E>())
+            .and_then(|queue| queue.downcast_ref::<Vec<E>>())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the number of events published this tick, of any type
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no events were published this tick
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Drops every queued event
+    pub fn clear(&mut self) {
+        self.queues.clear();
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Ping(u32);
+
+    #[derive(Debug, PartialEq)]
+    struct Pong;
+
+    #[test]
+    fn test_events_are_typed_and_ordered() {
+        let mut bus = EventBus::new();
+        bus.publish(Ping(1));
+        bus.publish(Pong);
+        bus.publish(Ping(2));
+
+        assert_eq!(bus.read::<Ping>(), [Ping(1), Ping(2)]);
+        assert_eq!(bus.read::<Pong>(), [Pong]);
+        assert!(bus.read::<u8>().is_empty());
+        assert_eq!(bus.len(), 3);
+
+        bus.clear();
+        assert!(bus.is_empty());
+        assert!(bus.read::<Ping>().is_empty());
+    }
+}
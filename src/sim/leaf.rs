@@ -1,62 +1,365 @@
 //! Leaf placement simulation
 //!
 //! Places leaves organically along invisible "vines" using Perlin noise for natural distribution.
+//!
+//! Each leaf grows, stays green for its lifespan, wilts, then detaches and falls out
+//! of sight. Once `max_leaves` exist, the recycle policy retires the oldest leaves
+//! so new ones keep sprouting.
+//!
+//! A wind field, sampled from the same Perlin noise, sways every leaf about its
+//! rotation. Gusts drift across the board in the wind's direction, and small or
+//! young leaves bend further than big, fully grown ones.
+
+use std::hash::Hasher;
 
 use noise::{NoiseFn, Perlin};
-use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use super::tictactoe::TicTacToeEvent;
+use super::{EventBus, Simulation, SimulationStats, StateHasher, rng};
+
+/// Noise cycles per world unit in the wind field; about one gust per board cell
+const WIND_SCALE: f64 = 0.8;
+
+/// Curve parameter steps per vine segment in the arc-length table
+const ARC_SAMPLES_PER_SEGMENT: usize = 32;
+
+/// Seed for placement and noise until the simulation is reseeded
+const DEFAULT_SEED: u32 = 42;
+
+/// Shape of a vine between its `start` and `end` points
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VineShape {
+    /// Straight segment
+    #[default]
+    Line,
+    /// Straight segments through these interior points
+    Polyline(Vec<[f32; 2]>),
+    /// Quadratic Bézier bent towards one control point
+    Quadratic([f32; 2]),
+    /// Cubic Bézier with two control points
+    Cubic([[f32; 2]; 2]),
+    /// Catmull-Rom spline passing smoothly through these interior points
+    CatmullRom(Vec<[f32; 2]>),
+}
 
-use super::Simulation;
+impl VineShape {
+    /// Returns the points between start and end that define the shape
+    pub fn points(&self) -> &[[f32; 2]] {
+        match self {
+            VineShape::Line => &[],
+            VineShape::Polyline(points) | VineShape::CatmullRom(points) => points,
+            VineShape::Quadratic(control) => std::slice::from_ref(control),
+            VineShape::Cubic(controls) => controls,
+        }
+    }
+}
 
-/// Invisible line along which leaves grow
-#[derive(Debug, Clone, Copy)]
+/// Invisible path along which leaves grow
+///
+/// Positions along a vine are fractions of its arc length, so evenly spaced
+/// positions are evenly spaced on the curve however it bends. The arc lengths are
+/// measured once at construction, so a vine's points can't change afterwards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "VinePath", into = "VinePath")]
 pub struct Vine {
-    pub start: [f32; 2],
-    pub end: [f32; 2],
+    start: [f32; 2],
+    end: [f32; 2],
+    shape: VineShape,
+    /// Arc length from the start at evenly spaced curve parameters
+    arc_lengths: Vec<f32>,
+}
+
+/// Serialized form of a [`Vine`]; the arc-length table is rebuilt on load
+#[derive(Serialize, Deserialize)]
+struct VinePath {
+    start: [f32; 2],
+    end: [f32; 2],
+    #[serde(default)]
+    shape: VineShape,
+}
+
+impl From<VinePath> for Vine {
+    fn from(path: VinePath) -> Self {
+        Self::with_shape(path.start, path.end, path.shape)
+    }
+}
+
+impl From<Vine> for VinePath {
+    fn from(vine: Vine) -> Self {
+        Self {
+            start: vine.start,
+            end: vine.end,
+            shape: vine.shape,
+        }
+    }
 }
 
 impl Vine {
+    /// Creates a straight vine
     pub fn new(start: [f32; 2], end: [f32; 2]) -> Self {
-        Self { start, end }
+        Self::with_shape(start, end, VineShape::Line)
+    }
+
+    /// Creates a vine of straight segments through `points`, at least two
+    pub fn polyline(points: &[[f32; 2]]) -> Self {
+        let (start, end, interior) = split_ends(points);
+        Self::with_shape(start, end, VineShape::Polyline(interior))
     }
 
-    /// Get point along vine at position t (0.0 = start, 1.0 = end)
+    /// Creates a quadratic Bézier vine
+    pub fn quadratic(start: [f32; 2], control: [f32; 2], end: [f32; 2]) -> Self {
+        Self::with_shape(start, end, VineShape::Quadratic(control))
+    }
+
+    /// Creates a cubic Bézier vine
+    pub fn cubic(start: [f32; 2], control1: [f32; 2], control2: [f32; 2], end: [f32; 2]) -> Self {
+        Self::with_shape(start, end, VineShape::Cubic([control1, control2]))
+    }
+
+    /// Creates a Catmull-Rom vine passing through `points`, at least two
+    pub fn catmull_rom(points: &[[f32; 2]]) -> Self {
+        let (start, end, interior) = split_ends(points);
+        Self::with_shape(start, end, VineShape::CatmullRom(interior))
+    }
+
+    /// Creates a vine of any shape
+    pub fn with_shape(start: [f32; 2], end: [f32; 2], shape: VineShape) -> Self {
+        let mut vine = Self {
+            start,
+            end,
+            shape,
+            arc_lengths: Vec::new(),
+        };
+        vine.arc_lengths = vine.measure();
+        vine
+    }
+
+    /// Returns the point the vine starts at
+    pub fn start(&self) -> [f32; 2] {
+        self.start
+    }
+
+    /// Returns the point the vine ends at
+    pub fn end(&self) -> [f32; 2] {
+        self.end
+    }
+
+    /// Returns the vine's shape
+    pub fn shape(&self) -> &VineShape {
+        &self.shape
+    }
+
+    /// Get point along vine at position t (0.0 = start, 1.0 = end), by arc length
     pub fn point_at(&self, t: f32) -> [f32; 2] {
-        [
-            self.start[0] + (self.end[0] - self.start[0]) * t,
-            self.start[1] + (self.end[1] - self.start[1]) * t,
-        ]
-    }
-
-    /// Get perpendicular direction (normalized)
-    pub fn perpendicular(&self) -> [f32; 2] {
-        let dx = self.end[0] - self.start[0];
-        let dy = self.end[1] - self.start[1];
-        let len = (dx * dx + dy * dy).sqrt();
-        if len > 0.0 {
-            [-dy / len, dx / len] // 90° rotation
-        } else {
-            [0.0, 1.0]
+        self.curve_point(self.curve_param(t))
+    }
+
+    /// Get unit direction of travel at position t
+    ///
+    /// Falls back to the start-to-end direction where the curve stalls, and to +x
+    /// for a vine with no extent.
+    pub fn tangent(&self, t: f32) -> [f32; 2] {
+        const STEP: f32 = 1.0e-3;
+        let u = self.curve_param(t);
+        let before = self.curve_point((u - STEP).max(0.0));
+        let after = self.curve_point((u + STEP).min(1.0));
+        normalize([after[0] - before[0], after[1] - before[1]])
+            .or_else(|| normalize([self.end[0] - self.start[0], self.end[1] - self.start[1]]))
+            .unwrap_or([1.0, 0.0])
+    }
+
+    /// Get perpendicular direction (normalized) at position t
+    pub fn perpendicular(&self, t: f32) -> [f32; 2] {
+        if self.length() <= 0.0 {
+            return [0.0, 1.0];
         }
+        let [dx, dy] = self.tangent(t);
+        [-dy, dx] // 90° rotation
     }
 
-    /// Get direction along vine (for leaf rotation)
-    pub fn direction_angle(&self) -> f32 {
-        let dx = self.end[0] - self.start[0];
-        let dy = self.end[1] - self.start[1];
+    /// Get direction along vine at position t (for leaf rotation)
+    pub fn direction_angle(&self, t: f32) -> f32 {
+        let [dx, dy] = self.tangent(t);
         dy.atan2(dx)
     }
 
-    /// Get length of the vine
-    fn length(&self) -> f32 {
-        let dx = self.end[0] - self.start[0];
-        let dy = self.end[1] - self.start[1];
-        (dx * dx + dy * dy).sqrt()
+    /// Get arc length of the vine
+    pub fn length(&self) -> f32 {
+        self.arc_lengths.last().copied().unwrap_or(0.0)
+    }
+
+    /// Returns start, the shape's points, and end, in order
+    fn knots(&self) -> Vec<[f32; 2]> {
+        let mut knots = vec![self.start];
+        knots.extend_from_slice(self.shape.points());
+        knots.push(self.end);
+        knots
+    }
+
+    /// Number of pieces the curve parameter is split into
+    fn segments(&self) -> usize {
+        match self.shape {
+            VineShape::Polyline(_) | VineShape::CatmullRom(_) => self.shape.points().len() + 1,
+            VineShape::Line | VineShape::Quadratic(_) | VineShape::Cubic(_) => 1,
+        }
+    }
+
+    /// Samples cumulative arc length along the curve parameter
+    fn measure(&self) -> Vec<f32> {
+        let samples = self.segments() * ARC_SAMPLES_PER_SEGMENT;
+        let mut lengths = Vec::with_capacity(samples + 1);
+        let mut total = 0.0;
+        let mut previous = self.start;
+        lengths.push(0.0);
+        for i in 1..=samples {
+            let point = self.curve_point(i as f32 / samples as f32);
+            total += distance(previous, point);
+            lengths.push(total);
+            previous = point;
+        }
+        lengths
     }
+
+    /// Converts an arc-length fraction into the curve parameter reaching it
+    fn curve_param(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        let length = self.length();
+        let steps = self.arc_lengths.len().saturating_sub(1);
+        if length <= 0.0 || steps == 0 {
+            return t;
+        }
+
+        let target = t * length;
+        let i = self
+            .arc_lengths
+            .partition_point(|&l| l < target)
+            .clamp(1, steps);
+        let (before, after) = (self.arc_lengths[i - 1], self.arc_lengths[i]);
+        let within = if after > before {
+            (target - before) / (after - before)
+        } else {
+            0.0
+        };
+        (i as f32 - 1.0 + within) / steps as f32
+    }
+
+    /// Evaluates the curve at parameter u (0.0-1.0), which isn't arc length
+    fn curve_point(&self, u: f32) -> [f32; 2] {
+        let (start, end) = (self.start, self.end);
+        match &self.shape {
+            VineShape::Line => lerp(start, end, u),
+            VineShape::Quadratic(c) => {
+                let v = 1.0 - u;
+                combine(&[(start, v * v), (*c, 2.0 * v * u), (end, u * u)])
+            }
+            VineShape::Cubic([c1, c2]) => {
+                let v = 1.0 - u;
+                combine(&[
+                    (start, v * v * v),
+                    (*c1, 3.0 * v * v * u),
+                    (*c2, 3.0 * v * u * u),
+                    (end, u * u * u),
+                ])
+            }
+            VineShape::Polyline(_) => {
+                let knots = self.knots();
+                let (i, local) = segment_at(u, knots.len() - 1);
+                lerp(knots[i], knots[i + 1], local)
+            }
+            VineShape::CatmullRom(_) => {
+                let knots = self.knots();
+                let last = knots.len() - 1;
+                let (i, local) = segment_at(u, last);
+                // Mirror the neighbours past each end so the curve starts and ends
+                // heading along its first and last segments
+                let p0 = if i == 0 {
+                    lerp(knots[1], knots[0], 2.0)
+                } else {
+                    knots[i - 1]
+                };
+                let p3 = if i + 1 == last {
+                    lerp(knots[last - 1], knots[last], 2.0)
+                } else {
+                    knots[i + 2]
+                };
+                catmull_rom(p0, knots[i], knots[i + 1], p3, local)
+            }
+        }
+    }
+}
+
+/// Splits a point list into its first point, last point, and those in between
+fn split_ends(points: &[[f32; 2]]) -> ([f32; 2], [f32; 2], Vec<[f32; 2]>) {
+    match points {
+        [] => ([0.0, 0.0], [0.0, 0.0], Vec::new()),
+        [only] => (*only, *only, Vec::new()),
+        [first, interior @ .., last] => (*first, *last, interior.to_vec()),
+    }
+}
+
+/// Returns which of `segments` equal pieces u falls in, and how far along it
+fn segment_at(u: f32, segments: usize) -> (usize, f32) {
+    let scaled = u.clamp(0.0, 1.0) * segments as f32;
+    let i = (scaled as usize).min(segments - 1);
+    (i, scaled - i as f32)
+}
+
+fn lerp(a: [f32; 2], b: [f32; 2], t: f32) -> [f32; 2] {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+}
+
+/// Sums weighted points
+fn combine(terms: &[([f32; 2], f32)]) -> [f32; 2] {
+    terms.iter().fold([0.0, 0.0], |sum, &(point, weight)| {
+        [sum[0] + point[0] * weight, sum[1] + point[1] * weight]
+    })
+}
+
+/// Uniform Catmull-Rom segment from `p1` to `p2`
+fn catmull_rom(p0: [f32; 2], p1: [f32; 2], p2: [f32; 2], p3: [f32; 2], t: f32) -> [f32; 2] {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    combine(&[
+        (p0, 0.5 * (-t3 + 2.0 * t2 - t)),
+        (p1, 0.5 * (3.0 * t3 - 5.0 * t2 + 2.0)),
+        (p2, 0.5 * (-3.0 * t3 + 4.0 * t2 + t)),
+        (p3, 0.5 * (t3 - t2)),
+    ])
+}
+
+fn distance(a: [f32; 2], b: [f32; 2]) -> f32 {
+    let dx = b[0] - a[0];
+    let dy = b[1] - a[1];
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// Returns `v` scaled to unit length, or None if it has no length
+fn normalize(v: [f32; 2]) -> Option<[f32; 2]> {
+    let len = (v[0] * v[0] + v[1] * v[1]).sqrt();
+    (len > 1.0e-6).then(|| [v[0] / len, v[1] / len])
+}
+
+/// Where a leaf is in its life
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LeafStage {
+    /// Unfurling from nothing to full size
+    #[default]
+    Growing,
+    /// Fully grown and green until its lifespan runs out
+    Mature,
+    /// Turning brown while still on the vine
+    Wilting,
+    /// Detached, drifting down and fading out
+    Falling,
 }
 
 /// Individual leaf instance (pure sim data)
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Leaf {
     pub position: [f32; 2],
     pub size: f32,
@@ -64,17 +367,152 @@ pub struct Leaf {
     pub rotation: f32,
     pub growth: f32,
     pub color_variant: u8,
+    /// Seconds since the leaf sprouted
+    #[serde(default)]
+    pub age: f32,
+    #[serde(default)]
+    pub stage: LeafStage,
+    /// Seconds spent in the current stage
+    #[serde(default)]
+    pub stage_time: f32,
+    /// Seconds this leaf stays mature before wilting
+    #[serde(default = "default_lifespan")]
+    pub lifespan: f32,
+    /// Colour shift from green (0.0) to brown (1.0)
+    #[serde(default)]
+    pub wilt: f32,
+    /// Fade while falling, from attached (0.0) to gone (1.0)
+    #[serde(default)]
+    pub fall: f32,
+    /// Current bend from the wind in radians, kept apart from `rotation` so the
+    /// sway never accumulates
+    #[serde(default)]
+    pub sway: f32,
+    /// Position at the start of the last tick, for blending between ticks
+    #[serde(default)]
+    pub previous_position: [f32; 2],
+    /// Swayed rotation at the start of the last tick
+    #[serde(default)]
+    pub previous_rotation: f32,
+}
+
+impl Leaf {
+    /// Returns true until the leaf detaches from its vine
+    pub fn is_attached(&self) -> bool {
+        self.stage != LeafStage::Falling
+    }
+
+    /// Returns how opaque to draw the leaf: fading in as it grows, out as it falls
+    pub fn opacity(&self) -> f32 {
+        self.growth * (1.0 - self.fall)
+    }
+
+    /// Returns the angle to draw the leaf at, including its wind sway
+    pub fn swayed_rotation(&self) -> f32 {
+        self.rotation + self.sway
+    }
+
+    /// Returns the position `alpha` (0.0-1.0) of the way from the last tick to this one
+    pub fn interpolated_position(&self, alpha: f32) -> [f32; 2] {
+        lerp(self.previous_position, self.position, alpha)
+    }
+
+    /// Returns the swayed rotation `alpha` (0.0-1.0) of the way from the last tick to this one
+    pub fn interpolated_rotation(&self, alpha: f32) -> f32 {
+        self.previous_rotation + (self.swayed_rotation() - self.previous_rotation) * alpha
+    }
+
+    /// Remembers the current pose as the starting point for the next tick's blend
+    fn record_previous(&mut self) {
+        self.previous_position = self.position;
+        self.previous_rotation = self.swayed_rotation();
+    }
+
+    /// Advances the leaf's life by `delta_time` seconds
+    fn age_by(&mut self, config: &LeafConfig, delta_time: f32) {
+        self.age += delta_time;
+        self.stage_time += delta_time;
+        match self.stage {
+            LeafStage::Growing => {
+                self.growth = (self.growth + config.growth_rate * delta_time).min(1.0);
+                if self.growth >= 1.0 {
+                    self.enter(LeafStage::Mature);
+                }
+            }
+            LeafStage::Mature => {
+                if self.stage_time >= self.lifespan {
+                    self.enter(LeafStage::Wilting);
+                }
+            }
+            LeafStage::Wilting => {
+                self.wilt = progress(self.stage_time, config.wilt_duration);
+                if self.wilt >= 1.0 {
+                    self.enter(LeafStage::Falling);
+                }
+            }
+            LeafStage::Falling => {
+                self.fall = progress(self.stage_time, config.fall_duration);
+                // Drop with a gentle side-to-side flutter (+y is down the screen)
+                let flutter = (self.age * 3.0).sin();
+                self.position[0] += flutter * config.fall_speed * 0.5 * delta_time;
+                self.position[1] += config.fall_speed * delta_time;
+                self.rotation += flutter * 1.5 * delta_time;
+            }
+        }
+    }
+
+    fn enter(&mut self, stage: LeafStage) {
+        self.stage = stage;
+        self.stage_time = 0.0;
+    }
+}
+
+fn default_lifespan() -> f32 {
+    LeafConfig::default().lifespan
+}
+
+/// Returns how far `elapsed` is through a stage lasting `duration` (0.0-1.0)
+fn progress(elapsed: f32, duration: f32) -> f32 {
+    if duration > 0.0 {
+        (elapsed / duration).min(1.0)
+    } else {
+        1.0
+    }
+}
+
+/// What happens once `max_leaves` leaves exist
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecyclePolicy {
+    /// Spawning waits until leaves die of old age
+    Never,
+    /// Each spawn that finds no room sends the oldest attached leaf wilting, so
+    /// new growth keeps replacing old
+    #[default]
+    OldestFirst,
 }
 
 /// Configuration for leaf simulation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct LeafConfig {
-    pub spawn_rate: f32,     // Leaves per second
-    pub growth_rate: f32,    // Growth units per second (0.0 → 1.0)
-    pub base_size: f32,      // Pixels (used as rendering radius)
-    pub size_variation: f32, // Fraction (0.0-1.0)
-    pub max_offset: f32,     // World space units (perpendicular distance from vine)
-    pub noise_seed: u32,     // Seed for Perlin noise
+    pub spawn_rate: f32,         // Leaves per second
+    pub growth_rate: f32,        // Growth units per second (0.0 → 1.0)
+    pub base_size: f32,          // Pixels (used as rendering radius)
+    pub size_variation: f32,     // Fraction (0.0-1.0)
+    pub max_offset: f32,         // World space units (perpendicular distance from vine)
+    pub max_leaves: usize,       // Population cap; see `recycle`
+    pub burst_leaves: usize,     // Leaves grown along a tic-tac-toe winning line
+    pub lifespan: f32,           // Seconds a grown leaf stays green before wilting
+    pub lifespan_variation: f32, // Fraction (0.0-1.0)
+    pub wilt_duration: f32,      // Seconds to turn brown before detaching
+    pub fall_duration: f32,      // Seconds to fall and fade out before removal
+    pub fall_speed: f32,         // World space units per second
+    pub recycle: RecyclePolicy,  // What happens once max_leaves exist
+    pub wind_strength: f32,      // Radians of sway in a full gust (0.0 = calm)
+    pub wind_direction: f32,     // Radians the wind blows towards (0 = +x, π/2 = down)
+    pub wind_gust_rate: f32,     // How quickly gusts rise, fall and travel
+    pub sway_stiffness: f32,     // How much size and growth stiffen a leaf against the wind
 }
 
 impl Default for LeafConfig {
@@ -85,22 +523,44 @@ impl Default for LeafConfig {
             base_size: 18.0,  // Pixels (rendering radius)
             size_variation: 0.3,
             max_offset: 0.05, // Decreased from 0.15 (3x tighter to vines)
-            noise_seed: 42,
+            max_leaves: 500,
+            burst_leaves: 12,
+            lifespan: 180.0,
+            lifespan_variation: 0.25,
+            wilt_duration: 6.0,
+            fall_duration: 3.0,
+            fall_speed: 0.4,
+            recycle: RecyclePolicy::OldestFirst,
+            wind_strength: 0.35,
+            wind_direction: 0.0,
+            wind_gust_rate: 0.4,
+            sway_stiffness: 1.0,
         }
     }
 }
 
 /// Leaf placement simulation
+#[derive(Clone, Serialize, Deserialize)]
 pub struct LeafSimulation {
     vines: Vec<Vine>,
     leaves: Vec<Leaf>,
     config: LeafConfig,
     spawn_accumulator: f32,
+    /// Rebuilt from `noise_seed` on load (Perlin is fully determined by its seed)
+    #[serde(skip)]
     noise: Perlin,
-    rng: StdRng,
+    noise_seed: u32,
+    rng: ChaCha12Rng,
+    rng_seed: u64,
     max_leaves: usize,
     active: bool,
     spawn_counter: usize, // Tracks total spawns for noise time evolution
+    /// Leaves sent wilting early to make room
+    #[serde(default)]
+    recycled: usize,
+    /// Seconds of wind so far, the time axis of the wind field
+    #[serde(default)]
+    wind_time: f64,
 }
 
 impl LeafSimulation {
@@ -108,20 +568,26 @@ impl LeafSimulation {
         Self::with_config(LeafConfig::default())
     }
 
+    /// Creates a simulation seeded with `DEFAULT_SEED`; a world reseeds it from its own seed
     pub fn with_config(config: LeafConfig) -> Self {
-        let rng = StdRng::seed_from_u64(config.noise_seed as u64);
-        let noise = Perlin::new(config.noise_seed);
+        let rng_seed = DEFAULT_SEED as u64;
+        let noise_seed = DEFAULT_SEED;
+        let max_leaves = config.max_leaves;
 
         Self {
             vines: Vec::new(),
             leaves: Vec::new(),
             config,
             spawn_accumulator: 0.0,
-            noise,
-            rng,
-            max_leaves: 500,
+            noise: Perlin::new(noise_seed),
+            noise_seed,
+            rng: ChaCha12Rng::seed_from_u64(rng_seed),
+            rng_seed,
+            max_leaves,
             active: true,
             spawn_counter: 0,
+            recycled: 0,
+            wind_time: 0.0,
         }
     }
 
@@ -166,7 +632,7 @@ impl LeafSimulation {
             return None;
         }
 
-        // Weight by length - longer vines more likely to be selected
+        // Weight by arc length - longer vines more likely to be selected
         let total_length: f32 = self.vines.iter().map(|v| v.length()).sum();
         if total_length <= 0.0 {
             // All zero-length vines, just pick random
@@ -207,15 +673,21 @@ impl LeafSimulation {
 
     fn generate_leaf(&mut self) -> Option<Leaf> {
         let vine_idx = self.select_random_vine()?;
-        let vine = &self.vines[vine_idx];
 
         // Sample position along vine
         let vine_pos = self.sample_vine_position(vine_idx);
-        let base_position = vine.point_at(vine_pos);
 
         // Sample perpendicular offset
         let offset_amount = self.sample_perpendicular_offset(vine_idx, vine_pos);
-        let perp = vine.perpendicular();
+
+        let vine = self.vines[vine_idx].clone();
+        Some(self.build_leaf(&vine, vine_pos, offset_amount))
+    }
+
+    /// Creates a leaf at `vine_pos` along `vine`, pushed `offset_amount` to its side
+    fn build_leaf(&mut self, vine: &Vine, vine_pos: f32, offset_amount: f32) -> Leaf {
+        let base_position = vine.point_at(vine_pos);
+        let perp = vine.perpendicular(vine_pos);
         let position = [
             base_position[0] + perp[0] * offset_amount,
             base_position[1] + perp[1] * offset_amount,
@@ -231,20 +703,108 @@ impl LeafSimulation {
         let aspect = self.rng.random_range(0.5..0.7);
 
         // Sample rotation based on vine direction
-        let base_angle = vine.direction_angle();
+        let base_angle = vine.direction_angle(vine_pos);
         let rotation = self.sample_rotation(base_angle);
 
         // Random color variant
         let color_variant = self.rng.random_range(0..4);
 
-        Some(Leaf {
+        let variation = self.config.lifespan_variation.clamp(0.0, 1.0);
+        let lifespan =
+            self.config.lifespan * self.rng.random_range(1.0 - variation..=1.0 + variation);
+
+        Leaf {
             position,
             size,
             aspect,
             rotation,
             growth: 0.0, // Starts at 0, will grow over time
             color_variant,
-        })
+            age: 0.0,
+            stage: LeafStage::Growing,
+            stage_time: 0.0,
+            lifespan,
+            wilt: 0.0,
+            fall: 0.0,
+            sway: 0.0,
+            previous_position: position,
+            previous_rotation: rotation,
+        }
+    }
+
+    /// Returns the wind's bend on `leaf` at the current wind time
+    ///
+    /// The gust strength comes from 3D Perlin noise over position and time, with the
+    /// sampling window sliding downwind so gusts visibly travel. Wind pushes hardest on
+    /// leaves lying across it, and stiffness grows with size and growth, so
+    /// seedlings and small leaves flutter while large mature ones barely move.
+    fn wind_sway(&self, leaf: &Leaf) -> f32 {
+        let config = &self.config;
+        if config.wind_strength == 0.0 {
+            return 0.0;
+        }
+
+        let travel = self.wind_time * config.wind_gust_rate as f64;
+        let direction = config.wind_direction as f64;
+        let gust = self.noise.get([
+            leaf.position[0] as f64 * WIND_SCALE - direction.cos() * travel,
+            leaf.position[1] as f64 * WIND_SCALE - direction.sin() * travel,
+            travel * 0.5,
+        ]);
+        // Map [-1, 1] → [0.0, 1.0]: lulls to full gusts, never blowing backwards
+        let gust = (gust as f32 + 1.0) * 0.5;
+
+        let push = config.wind_strength * gust * (config.wind_direction - leaf.rotation).sin();
+        let relative_size = if config.base_size > 0.0 {
+            leaf.size / config.base_size
+        } else {
+            1.0
+        };
+        let stiffness = 1.0 + config.sway_stiffness.max(0.0) * relative_size * leaf.growth;
+        push / stiffness
+    }
+
+    /// Sends the oldest leaf still on a vine, and not yet wilting, into its wilt
+    /// Returns false if every leaf is already on its way out
+    ///
+    /// Mature leaves go first; a growing leaf is only retired when none are mature,
+    /// and wilts at the size it had reached.
+    fn retire_oldest(&mut self) -> bool {
+        let oldest_in = |leaves: &[Leaf], stage| {
+            leaves
+                .iter()
+                .enumerate()
+                .filter(|(_, leaf)| leaf.stage == stage)
+                .max_by(|(_, a), (_, b)| a.age.total_cmp(&b.age))
+                .map(|(index, _)| index)
+        };
+        let oldest = oldest_in(&self.leaves, LeafStage::Mature)
+            .or_else(|| oldest_in(&self.leaves, LeafStage::Growing));
+        match oldest {
+            Some(index) => {
+                self.leaves[index].enter(LeafStage::Wilting);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Grows `burst_leaves` leaves spread evenly from `start` to `end`
+    fn grow_burst(&mut self, start: [f32; 2], end: [f32; 2]) {
+        let line = Vine::new(start, end);
+        let count = self.config.burst_leaves;
+        for i in 0..count {
+            if self.leaves.len() >= self.max_leaves {
+                break;
+            }
+
+            let vine_pos = (i as f32 + 0.5) / count as f32;
+            let offset_amount = self
+                .rng
+                .random_range(-self.config.max_offset..=self.config.max_offset);
+            let leaf = self.build_leaf(&line, vine_pos, offset_amount);
+            self.leaves.push(leaf);
+        }
     }
 }
 
@@ -256,6 +816,11 @@ impl Default for LeafSimulation {
 
 impl Simulation for LeafSimulation {
     fn tick(&mut self, delta_time: f32) {
+        // Leaves that don't move this tick must not keep blending from a stale pose
+        for leaf in &mut self.leaves {
+            leaf.record_previous();
+        }
+
         if !self.active {
             return;
         }
@@ -265,25 +830,35 @@ impl Simulation for LeafSimulation {
             return;
         }
 
-        // Phase 1: Grow existing leaves
+        // Phase 1: Grow, age and wilt existing leaves
         for leaf in &mut self.leaves {
-            if leaf.growth < 1.0 {
-                leaf.growth = (leaf.growth + self.config.growth_rate * delta_time).min(1.0);
-            }
+            leaf.age_by(&self.config, delta_time);
         }
 
-        // Phase 2: Spawn new leaves (fixed timestep)
+        // Phase 2: Sway leaves in the wind
+        self.wind_time += delta_time as f64;
+        for i in 0..self.leaves.len() {
+            self.leaves[i].sway = self.wind_sway(&self.leaves[i]);
+        }
+
+        // Phase 3: Remove leaves that have finished falling
+        self.leaves
+            .retain(|leaf| leaf.is_attached() || leaf.fall < 1.0);
+
+        // Phase 4: Spawn new leaves (fixed timestep), making room by recycling
         self.spawn_accumulator += delta_time;
         let spawn_interval = 1.0 / self.config.spawn_rate;
 
         while self.spawn_accumulator >= spawn_interval {
             self.spawn_accumulator -= spawn_interval;
 
-            if self.leaves.len() < self.max_leaves
-                && let Some(new_leaf) = self.generate_leaf()
-            {
-                self.leaves.push(new_leaf);
-                self.spawn_counter += 1;
+            if self.leaves.len() < self.max_leaves {
+                if let Some(new_leaf) = self.generate_leaf() {
+                    self.leaves.push(new_leaf);
+                    self.spawn_counter += 1;
+                }
+            } else if self.config.recycle == RecyclePolicy::OldestFirst && self.retire_oldest() {
+                self.recycled += 1;
             }
         }
     }
@@ -292,13 +867,137 @@ impl Simulation for LeafSimulation {
         self.leaves.clear();
         self.spawn_accumulator = 0.0;
         self.spawn_counter = 0;
+        self.recycled = 0;
+        self.wind_time = 0.0;
+        // Restart the placement stream so a reset replays the same leaves
+        self.rng = ChaCha12Rng::seed_from_u64(self.rng_seed);
         // Note: vines are preserved
     }
 
+    fn reseed(&mut self, seed: u64) {
+        // Independent streams for random draws and noise-driven placement
+        self.rng_seed = rng::derive_seed(seed, "placement");
+        self.rng = ChaCha12Rng::seed_from_u64(self.rng_seed);
+        self.noise_seed = rng::derive_seed(seed, "noise") as u32;
+        self.noise = Perlin::new(self.noise_seed);
+    }
+
     fn name(&self) -> &str {
         "leaf"
     }
 
+    fn runs_after(&self) -> &[&str] {
+        &["tictactoe"]
+    }
+
+    fn handle_events(&mut self, events: &EventBus) {
+        if !self.active {
+            return;
+        }
+
+        for event in events.read::<TicTacToeEvent>() {
+            if let TicTacToeEvent::GameWon { line, config, .. } = event
+                && let (Some(first), Some(last)) = (line.first(), line.last())
+            {
+                self.grow_burst(
+                    config.cell_center(first.0, first.1),
+                    config.cell_center(last.0, last.1),
+                );
+            }
+        }
+    }
+
+    fn memory_footprint(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.vines.capacity() * std::mem::size_of::<Vine>()
+            + self
+                .vines
+                .iter()
+                .map(|vine| vine.arc_lengths.capacity() * std::mem::size_of::<f32>())
+                .sum::<usize>()
+            + self.leaves.capacity() * std::mem::size_of::<Leaf>()
+    }
+
+    fn save_state(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+
+    fn stats(&self) -> SimulationStats {
+        let grown = self.leaves.iter().filter(|leaf| leaf.growth >= 1.0).count();
+        let in_stage = |stage| {
+            self.leaves
+                .iter()
+                .filter(|leaf| leaf.stage == stage)
+                .count()
+        };
+        let mut stats = SimulationStats::new();
+        stats.insert("leaves".into(), self.leaves.len().into());
+        stats.insert("fully_grown".into(), grown.into());
+        stats.insert("wilting".into(), in_stage(LeafStage::Wilting).into());
+        stats.insert("falling".into(), in_stage(LeafStage::Falling).into());
+        stats.insert("recycled".into(), self.recycled.into());
+        stats.insert("vines".into(), self.vines.len().into());
+        stats.insert("spawned".into(), self.spawn_counter.into());
+        stats
+    }
+
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+        for vine in &self.vines {
+            hasher.write_u8(match vine.shape {
+                VineShape::Line => 0,
+                VineShape::Polyline(_) => 1,
+                VineShape::Quadratic(_) => 2,
+                VineShape::Cubic(_) => 3,
+                VineShape::CatmullRom(_) => 4,
+            });
+            for value in [vine.start, vine.end]
+                .iter()
+                .chain(vine.shape.points())
+                .flatten()
+            {
+                hasher.write_f32(*value);
+            }
+        }
+        for leaf in &self.leaves {
+            hasher.write_f32(leaf.position[0]);
+            hasher.write_f32(leaf.position[1]);
+            hasher.write_f32(leaf.size);
+            hasher.write_f32(leaf.aspect);
+            hasher.write_f32(leaf.rotation);
+            hasher.write_f32(leaf.growth);
+            hasher.write_u8(leaf.color_variant);
+            hasher.write_f32(leaf.age);
+            hasher.write_u8(leaf.stage as u8);
+            hasher.write_f32(leaf.stage_time);
+            hasher.write_f32(leaf.lifespan);
+            hasher.write_f32(leaf.wilt);
+            hasher.write_f32(leaf.fall);
+            hasher.write_f32(leaf.sway);
+            hasher.write_f32(leaf.previous_position[0]);
+            hasher.write_f32(leaf.previous_position[1]);
+            hasher.write_f32(leaf.previous_rotation);
+        }
+        hasher.write_f32(self.spawn_accumulator);
+        hasher.write_usize(self.spawn_counter);
+        hasher.write_usize(self.recycled);
+        hasher.write_f64(self.wind_time);
+        hasher.write_usize(self.max_leaves);
+        hasher.write_u8(self.active as u8);
+        hasher.write_u32(self.noise_seed);
+        // The seed plus the stream position fully determine the RNG state
+        hasher.write_u64(self.rng_seed);
+        hasher.write_u128(self.rng.get_word_pos());
+        hasher.finish()
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), serde_json::Error> {
+        let mut restored: LeafSimulation = serde_json::from_value(state)?;
+        restored.noise = Perlin::new(restored.noise_seed);
+        *self = restored;
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -362,7 +1061,7 @@ mod tests {
     #[test]
     fn test_vine_perpendicular() {
         let vine = Vine::new([0.0, 0.0], [100.0, 0.0]);
-        let perp = vine.perpendicular();
+        let perp = vine.perpendicular(0.5);
         // Horizontal line → vertical perpendicular
         assert!((perp[0]).abs() < 0.001);
         assert!((perp[1] - 1.0).abs() < 0.001 || (perp[1] + 1.0).abs() < 0.001);
@@ -371,22 +1070,107 @@ mod tests {
     #[test]
     fn test_vine_direction_angle() {
         let vine = Vine::new([0.0, 0.0], [100.0, 0.0]);
-        let angle = vine.direction_angle();
+        let angle = vine.direction_angle(0.5);
         assert!((angle - 0.0).abs() < 0.001); // Horizontal = 0 radians
 
         let vine2 = Vine::new([0.0, 0.0], [0.0, 100.0]);
-        let angle2 = vine2.direction_angle();
+        let angle2 = vine2.direction_angle(0.5);
         assert!((angle2 - std::f32::consts::FRAC_PI_2).abs() < 0.001); // Vertical = π/2
     }
 
     #[test]
     fn test_zero_length_vine() {
         let vine = Vine::new([50.0, 50.0], [50.0, 50.0]);
-        let perp = vine.perpendicular();
+        let perp = vine.perpendicular(0.5);
         assert_eq!(perp, [0.0, 1.0]); // Default perpendicular
         assert_eq!(vine.length(), 0.0);
     }
 
+    #[test]
+    fn test_polyline_spacing_follows_arc_length() {
+        // 30 units right, then 10 up: a quarter of the way is still on the first leg
+        let vine = Vine::polyline(&[[0.0, 0.0], [30.0, 0.0], [30.0, 10.0]]);
+        assert!((vine.length() - 40.0).abs() < 0.01);
+        let quarter = vine.point_at(0.25);
+        assert!((quarter[0] - 10.0).abs() < 0.01 && quarter[1].abs() < 0.01);
+        let late = vine.point_at(0.875);
+        assert!((late[0] - 30.0).abs() < 0.01 && (late[1] - 5.0).abs() < 0.01);
+        assert_eq!(vine.point_at(0.0), [0.0, 0.0]);
+        assert_eq!(vine.point_at(1.0), [30.0, 10.0]);
+
+        // Tangents turn with the path
+        assert!((vine.direction_angle(0.25)).abs() < 0.01);
+        let up = vine.perpendicular(0.25);
+        assert!(up[0].abs() < 0.01 && (up[1] - 1.0).abs() < 0.01);
+        assert!((vine.direction_angle(0.9) - std::f32::consts::FRAC_PI_2).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_bezier_vines() {
+        // Symmetric arch: peaks halfway along with a horizontal tangent
+        let arch = Vine::quadratic([0.0, 0.0], [50.0, 100.0], [100.0, 0.0]);
+        let top = arch.point_at(0.5);
+        assert!((top[0] - 50.0).abs() < 0.1 && (top[1] - 50.0).abs() < 0.1);
+        assert!(arch.direction_angle(0.5).abs() < 0.01);
+        assert!(arch.direction_angle(0.0) > 0.5);
+        assert!(arch.length() > 100.0);
+
+        // Even spacing by arc length, even where the parameter bunches up
+        let cubic = Vine::cubic([0.0, 0.0], [90.0, 0.0], [100.0, 0.0], [100.0, 0.0]);
+        for i in 0..=4 {
+            let point = cubic.point_at(i as f32 / 4.0);
+            assert!((point[0] - 25.0 * i as f32).abs() < 0.5, "{point:?}");
+        }
+    }
+
+    #[test]
+    fn test_catmull_rom_passes_through_points() {
+        let points = [[0.0, 0.0], [10.0, 5.0], [20.0, 0.0], [30.0, 5.0]];
+        let vine = Vine::catmull_rom(&points);
+        assert_eq!(vine.start(), points[0]);
+        assert_eq!(vine.end(), points[3]);
+        assert_eq!(vine.shape().points(), &points[1..3]);
+
+        for &knot in &points {
+            let closest = (0..=1000)
+                .map(|i| distance(vine.point_at(i as f32 / 1000.0), knot))
+                .fold(f32::MAX, f32::min);
+            assert!(closest < 0.1, "{knot:?} missed by {closest}");
+        }
+        assert!(vine.length() > 30.0);
+    }
+
+    #[test]
+    fn test_vine_serde_rebuilds_arc_lengths() {
+        // Straight vines saved before shapes existed still load
+        let old: Vine = serde_json::from_str(r#"{"start":[0.0,0.0],"end":[3.0,4.0]}"#).unwrap();
+        assert_eq!(old.shape(), &VineShape::Line);
+        assert!((old.length() - 5.0).abs() < 1.0e-4);
+
+        let vine = Vine::quadratic([0.0, 0.0], [50.0, 100.0], [100.0, 0.0]);
+        let restored: Vine = serde_json::from_value(serde_json::to_value(&vine).unwrap()).unwrap();
+        assert_eq!(restored.shape(), vine.shape());
+        assert_eq!(restored.length(), vine.length());
+        assert_eq!(restored.point_at(0.3), vine.point_at(0.3));
+    }
+
+    #[test]
+    fn test_curved_leaves_follow_vine() {
+        let mut sim = LeafSimulation::with_config(LeafConfig {
+            spawn_rate: 20.0,
+            max_offset: 0.0,
+            ..Default::default()
+        });
+        sim.add_vine(Vine::polyline(&[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]]));
+        sim.tick(1.0);
+
+        assert!(!sim.leaves().is_empty());
+        for leaf in sim.leaves() {
+            let [x, y] = leaf.position;
+            assert!(y.abs() < 0.01 || (x - 10.0).abs() < 0.01, "{x}, {y}");
+        }
+    }
+
     #[test]
     fn test_spawning_over_time() {
         let mut sim = LeafSimulation::new();
@@ -464,6 +1248,238 @@ mod tests {
         assert_eq!(sim.leaves().len(), 10);
     }
 
+    /// Config with a quick lifecycle and no lifespan variation
+    fn short_lived() -> LeafConfig {
+        LeafConfig {
+            spawn_rate: 10.0,
+            growth_rate: 2.0,
+            lifespan: 1.0,
+            lifespan_variation: 0.0,
+            wilt_duration: 1.0,
+            fall_duration: 1.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_leaf_lifecycle_stages() {
+        let mut sim = LeafSimulation::with_config(LeafConfig {
+            spawn_rate: 1.0,
+            ..short_lived()
+        });
+        sim.add_vine(test_vine());
+        sim.tick(1.0);
+        assert_eq!(sim.leaves().len(), 1);
+        sim.config.spawn_rate = 1.0e-6; // No more spawns
+
+        // Follow the leaf until it is gone: 0.5s growing, 1s each mature, wilting, falling
+        let start = sim.leaves()[0].position;
+        let mut stages = vec![LeafStage::Growing];
+        for _ in 0..80 {
+            let Some(&leaf) = sim.leaves().first() else {
+                break;
+            };
+            if stages.last() != Some(&leaf.stage) {
+                stages.push(leaf.stage);
+            }
+            if leaf.stage == LeafStage::Falling && leaf.fall > 0.0 {
+                assert_eq!(leaf.wilt, 1.0);
+                assert!(leaf.position[1] > start[1], "falling leaves drop");
+                assert!(leaf.opacity() <= 1.0 - leaf.fall);
+            }
+            sim.tick(0.05);
+        }
+
+        assert!(sim.leaves().is_empty());
+        assert_eq!(
+            stages,
+            [
+                LeafStage::Growing,
+                LeafStage::Mature,
+                LeafStage::Wilting,
+                LeafStage::Falling
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interpolation_blends_from_previous_tick() {
+        let mut sim = LeafSimulation::with_config(LeafConfig {
+            spawn_rate: 1.0,
+            ..short_lived()
+        });
+        sim.add_vine(test_vine());
+        sim.tick(1.0);
+        sim.config.spawn_rate = 1.0e-6;
+
+        // Run the leaf into its fall so both position and rotation change per tick
+        while sim.leaves()[0].stage != LeafStage::Falling {
+            sim.tick(0.05);
+        }
+        let before = sim.leaves()[0];
+        sim.tick(0.05);
+        let leaf = sim.leaves()[0];
+
+        assert_eq!(leaf.previous_position, before.position);
+        assert_eq!(leaf.previous_rotation, before.swayed_rotation());
+        assert_eq!(leaf.interpolated_position(0.0), before.position);
+        assert_eq!(leaf.interpolated_position(1.0), leaf.position);
+        assert_eq!(leaf.interpolated_rotation(1.0), leaf.swayed_rotation());
+        let halfway = leaf.interpolated_position(0.5)[1];
+        assert!(before.position[1] < halfway && halfway < leaf.position[1]);
+    }
+
+    #[test]
+    fn test_recycling_keeps_spawning_at_cap() {
+        let mut sim = LeafSimulation::with_config(LeafConfig {
+            spawn_rate: 20.0,
+            lifespan: 1000.0,
+            ..short_lived()
+        });
+        sim.set_max_leaves(10);
+        sim.add_vine(test_vine());
+
+        for _ in 0..100 {
+            sim.tick(0.1);
+        }
+        assert!(sim.leaves().len() <= 10);
+        // Each recycled leaf takes 2s to wilt and fall before its slot frees up
+        assert!(sim.spawn_counter > 30, "spawned {}", sim.spawn_counter);
+        assert!(sim.recycled > 0);
+        // Recycling retires the oldest first, so none of the first leaves survive
+        assert!(sim.leaves().iter().all(|leaf| leaf.age < 9.0));
+
+        // Without recycling, spawning waits for leaves to die of old age
+        let mut frozen = LeafSimulation::with_config(LeafConfig {
+            spawn_rate: 20.0,
+            lifespan: 1000.0,
+            recycle: RecyclePolicy::Never,
+            ..short_lived()
+        });
+        frozen.set_max_leaves(10);
+        frozen.add_vine(test_vine());
+        for _ in 0..100 {
+            frozen.tick(0.1);
+        }
+        assert_eq!(frozen.spawn_counter, 10);
+        assert_eq!(frozen.recycled, 0);
+    }
+
+    #[test]
+    fn test_recycling_prefers_mature_and_never_grows_leaves() {
+        let mut sim = LeafSimulation::with_config(LeafConfig {
+            spawn_rate: 1.0,
+            growth_rate: 0.1,
+            lifespan: 1000.0,
+            ..Default::default()
+        });
+        sim.set_max_leaves(3);
+        sim.add_vine(test_vine());
+        for _ in 0..3 {
+            sim.tick(1.0);
+        }
+        assert_eq!(sim.leaves().len(), 3);
+
+        // The youngest is mature, the older two are still growing
+        sim.leaves[2].growth = 1.0;
+        sim.leaves[2].enter(LeafStage::Mature);
+        sim.tick(1.0);
+        assert_eq!(sim.leaves[2].stage, LeafStage::Wilting);
+        assert_eq!(sim.leaves[0].stage, LeafStage::Growing);
+
+        // With no mature leaves left, the oldest growing one wilts at its size
+        let growth = sim.leaves[0].growth;
+        sim.tick(1.0);
+        assert_eq!(sim.leaves[0].stage, LeafStage::Wilting);
+        // It grew during the tick's aging phase, before being retired
+        let retired = sim.leaves[0].growth;
+        assert!(
+            (retired - (growth + 0.1)).abs() < 1.0e-5,
+            "{growth} → {retired}"
+        );
+
+        // And stops growing once wilting
+        sim.tick(1.0);
+        assert_eq!(sim.leaves[0].growth, retired);
+    }
+
+    #[test]
+    fn test_leaves_saved_before_lifecycle_load() {
+        let leaf: Leaf = serde_json::from_str(
+            r#"{"position":[0.0,0.0],"size":18.0,"aspect":0.6,"rotation":0.0,"growth":1.0,"color_variant":2}"#,
+        )
+        .unwrap();
+        assert_eq!(leaf.stage, LeafStage::Growing);
+        assert_eq!(leaf.lifespan, LeafConfig::default().lifespan);
+        assert_eq!(leaf.opacity(), 1.0);
+    }
+
+    #[test]
+    fn test_wind_sways_without_drifting() {
+        let mut sim = LeafSimulation::with_config(LeafConfig {
+            spawn_rate: 10.0,
+            lifespan: 1000.0,
+            ..Default::default()
+        });
+        sim.add_vine(test_vine());
+        sim.tick(1.0);
+        sim.config.spawn_rate = 1.0e-6; // No more spawns
+        sim.tick(1.0); // Grow fully
+
+        let rotations: Vec<_> = sim.leaves().iter().map(|leaf| leaf.rotation).collect();
+        let mut sways = Vec::new();
+        for _ in 0..600 {
+            sim.tick(1.0 / 60.0);
+            sways.push(sim.leaves()[0].sway);
+            for leaf in sim.leaves() {
+                assert!(leaf.sway.abs() <= sim.config.wind_strength);
+                assert_eq!(leaf.swayed_rotation(), leaf.rotation + leaf.sway);
+            }
+        }
+
+        // The wind changes over time, but only the sway moves
+        let (min, max) = sways.iter().fold((f32::MAX, f32::MIN), |(lo, hi), &sway| {
+            (lo.min(sway), hi.max(sway))
+        });
+        assert!(max - min > 0.001, "sway stayed at {min}");
+        let after: Vec<_> = sim.leaves().iter().map(|leaf| leaf.rotation).collect();
+        assert_eq!(rotations, after);
+    }
+
+    #[test]
+    fn test_big_grown_leaves_are_stiffer() {
+        let mut sim = LeafSimulation::with_config(LeafConfig {
+            wind_direction: std::f32::consts::FRAC_PI_2,
+            ..Default::default()
+        });
+        sim.add_vine(test_vine());
+        sim.config.spawn_rate = 10.0;
+        sim.tick(0.1);
+        sim.wind_time = 3.0;
+
+        let mut leaf = sim.leaves()[0];
+        leaf.position = [0.3, 0.2];
+        leaf.rotation = 0.0; // Across the wind
+        leaf.size = sim.config.base_size;
+        leaf.growth = 0.0;
+        let seedling = sim.wind_sway(&leaf);
+        leaf.growth = 1.0;
+        let grown = sim.wind_sway(&leaf);
+        leaf.size *= 2.0;
+        let big = sim.wind_sway(&leaf);
+        assert!(
+            seedling > grown && grown > big && big > 0.0,
+            "{seedling} {grown} {big}"
+        );
+
+        // Leaves lying along the wind don't bend, and calm air bends nothing
+        leaf.rotation = std::f32::consts::FRAC_PI_2;
+        assert!(sim.wind_sway(&leaf).abs() < 1.0e-6);
+        leaf.rotation = 0.0;
+        sim.config.wind_strength = 0.0;
+        assert_eq!(sim.wind_sway(&leaf), 0.0);
+    }
+
     #[test]
     fn test_no_spawning_without_vines() {
         let mut sim = LeafSimulation::new();
@@ -519,13 +1535,14 @@ mod tests {
     #[test]
     fn test_determinism_same_seed() {
         let config = LeafConfig {
-            noise_seed: 12345,
             spawn_rate: 5.0,
             ..Default::default()
         };
 
         let mut sim1 = LeafSimulation::with_config(config.clone());
         let mut sim2 = LeafSimulation::with_config(config);
+        sim1.reseed(12345);
+        sim2.reseed(12345);
 
         sim1.add_vine(test_vine());
         sim2.add_vine(test_vine());
@@ -548,19 +1565,15 @@ mod tests {
 
     #[test]
     fn test_different_seeds_different_results() {
-        let config1 = LeafConfig {
-            noise_seed: 111,
-            spawn_rate: 5.0,
-            ..Default::default()
-        };
-        let config2 = LeafConfig {
-            noise_seed: 222,
+        let config = LeafConfig {
             spawn_rate: 5.0,
             ..Default::default()
         };
 
-        let mut sim1 = LeafSimulation::with_config(config1);
-        let mut sim2 = LeafSimulation::with_config(config2);
+        let mut sim1 = LeafSimulation::with_config(config.clone());
+        let mut sim2 = LeafSimulation::with_config(config);
+        sim1.reseed(111);
+        sim2.reseed(222);
 
         sim1.add_vine(test_vine());
         sim2.add_vine(test_vine());
@@ -656,7 +1669,8 @@ mod tests {
 
             for vine in sim.vines() {
                 // Calculate perpendicular distance from leaf to vine
-                let distance = distance_point_to_line_segment(leaf.position, vine.start, vine.end);
+                let distance =
+                    distance_point_to_line_segment(leaf.position, vine.start(), vine.end());
                 min_distance = min_distance.min(distance);
             }
 
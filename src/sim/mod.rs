@@ -2,25 +2,86 @@
 //!
 //! Handles game state, logic, physics, and entities.
 
+pub mod checksum;
+pub mod events;
 pub mod leaf;
+pub mod profile;
+pub mod registry;
+pub mod replay;
+pub mod rewind;
+pub mod rng;
+pub mod schedule;
+pub mod snapshot;
 pub mod tictactoe;
+pub mod ultimate;
 
 use std::any::Any;
+use std::hash::Hasher;
+use std::path::Path;
+use std::time::Instant;
 
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+pub use checksum::{ChecksumConfig, ChecksumHistory, StateHasher, TickChecksum};
+pub use events::EventBus;
 pub use leaf::LeafSimulation;
+pub use profile::{Profiler, SimulationProfile};
+pub use registry::{SimConfig, SimulationEntry, SimulationRegistry};
+pub use replay::{Replay, ReplayPlayer, ReplayRecorder, WorldAction};
+pub use rewind::{RewindBuffer, RewindConfig, RewindFrame};
+pub use schedule::{ScheduleError, TickPhase};
+pub use snapshot::{SimulationState, SnapshotError, SnapshotHeader, WorldSnapshot};
 pub use tictactoe::TicTacToeSimulation;
+pub use ultimate::UltimateTicTacToeSimulation;
+
+/// Object-safe cloning for boxed simulations
+///
+/// Implemented automatically for every `Simulation` that is `Clone`.
+pub trait SimulationClone {
+    /// Clones the simulation into a new box
+    fn clone_box(&self) -> Box<dyn Simulation>;
+}
+
+impl<T: Simulation + Clone + 'static> SimulationClone for T {
+    fn clone_box(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Simulation> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
 
 /// Trait that all game simulations must implement
 ///
 /// This allows the World to contain and manage multiple different game systems
 /// in a pluggable way. Each simulation is responsible for its own state and logic.
-pub trait Simulation {
+pub trait Simulation: SimulationClone {
+    /// Runs before any simulation's `tick` this tick
+    fn pre_tick(&mut self, _delta_time: f32) {}
+
     /// Updates the simulation by one tick
     ///
     /// # Arguments
     /// * `delta_time` - Time elapsed since last tick in seconds
     fn tick(&mut self, delta_time: f32);
 
+    /// Runs after every simulation's `tick` this tick
+    fn post_tick(&mut self, _delta_time: f32) {}
+
+    /// Publishes events queued since the last tick
+    ///
+    /// Called after the `tick` phase, in schedule order.
+    fn publish_events(&mut self, _events: &mut EventBus) {}
+
+    /// Reacts to every event published this tick
+    ///
+    /// Called once all simulations have published, in schedule order, before `post_tick`.
+    fn handle_events(&mut self, _events: &EventBus) {}
+
     /// Resets the simulation to its initial state
     fn reset(&mut self);
 
@@ -32,6 +93,60 @@ pub trait Simulation {
         true
     }
 
+    /// Names of simulations that must run before this one in every phase
+    ///
+    /// Names that don't match a simulation in the world are ignored.
+    fn runs_after(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Seeds the simulation's random number generators
+    ///
+    /// The World calls this with a seed derived from the world seed and the
+    /// simulation's name whenever the simulation is added or the world is reseeded.
+    /// Simulations without randomness can ignore it.
+    fn reseed(&mut self, _seed: u64) {}
+
+    /// Approximate memory used by the simulation's state, in bytes
+    ///
+    /// Used to budget the rewind buffer. Simulations holding heap data should add it.
+    fn memory_footprint(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+
+    /// Serializes the complete internal state for snapshots
+    ///
+    /// Restoring the returned value with `load_state` must reproduce the
+    /// simulation bit-exact, including any RNG state.
+    fn save_state(&self) -> Result<serde_json::Value, serde_json::Error>;
+
+    /// Restores internal state previously produced by `save_state`
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), serde_json::Error>;
+
+    /// Summary statistics for reports such as the headless runner's
+    fn stats(&self) -> SimulationStats {
+        SimulationStats::new()
+    }
+
+    /// Hashes the complete internal state for desync detection
+    ///
+    /// Equal states must give equal hashes across runs and platforms, so use a
+    /// [`StateHasher`] and hash floats by their bits. The default hashes the
+    /// serialized `save_state` output; override it to avoid serializing every tick.
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+        match self
+            .save_state()
+            .and_then(|state| serde_json::to_vec(&state))
+        {
+            Ok(bytes) => hasher.write(&bytes),
+            Err(e) => {
+                warn!(simulation = self.name(), error = %e, "Failed to serialize state for hashing")
+            }
+        }
+        hasher.finish()
+    }
+
     /// Allows downcasting to concrete types for specific operations
     ///
     /// This enables type-safe access to simulation-specific methods
@@ -41,6 +156,47 @@ pub trait Simulation {
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
+/// Per-simulation pause state and time multiplier, applied on top of the world's
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulationControl {
+    /// Skip this simulation's ticks and events while the rest of the world runs
+    pub paused: bool,
+    /// Multiplier applied to the delta time this simulation receives
+    pub time_scale: f32,
+}
+
+impl Default for SimulationControl {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            time_scale: 1.0,
+        }
+    }
+}
+
+/// Named summary values reported by [`Simulation::stats`]
+pub type SimulationStats = serde_json::Map<String, serde_json::Value>;
+
+/// Fixed timestep settings used by [`World::advance`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimestepConfig {
+    /// Duration of a single simulation tick in seconds
+    pub step: f32,
+    /// Maximum number of ticks run per frame; any backlog beyond this is dropped
+    pub max_substeps: u32,
+}
+
+impl Default for TimestepConfig {
+    fn default() -> Self {
+        Self {
+            step: 1.0 / 60.0,
+            max_substeps: 8,
+        }
+    }
+}
+
 /// Game world state
 pub struct World {
     /// Total number of simulation ticks elapsed
@@ -51,12 +207,32 @@ pub struct World {
     time_scale: f32,
     /// Accumulator for fixed timestep simulation
     timestep_accumulator: f32,
+    /// Fixed timestep settings
+    timestep: TimestepConfig,
     /// Whether the simulation is paused
     paused: bool,
     /// Random number generator seed
     rng_seed: u64,
     /// Collection of all active simulations
     simulations: Vec<Box<dyn Simulation>>,
+    /// Pause state and time multiplier for each entry in `simulations`
+    controls: Vec<SimulationControl>,
+    /// Indices into `simulations` in tick order
+    schedule: Vec<usize>,
+    /// Events published during the most recent tick
+    events: EventBus,
+    /// Wall-clock time each simulation spends per tick
+    profiler: Profiler,
+    /// Recent world checksums
+    checksums: ChecksumHistory,
+    /// First tick where playback diverged from the replay's recorded checksums
+    replay_divergence: Option<u64>,
+    /// Records applied actions while a replay is being captured
+    recorder: Option<ReplayRecorder>,
+    /// Feeds recorded actions back while a replay is playing
+    player: Option<ReplayPlayer>,
+    /// In-memory history for stepping backwards
+    rewind: Option<RewindBuffer>,
 }
 
 impl World {
@@ -65,9 +241,32 @@ impl World {
         Self::default()
     }
 
+    /// Creates a world with a random seed and no simulations
+    pub fn empty() -> Self {
+        Self {
+            tick_count: 0,
+            sim_time: 0.0,
+            time_scale: 1.0,
+            timestep_accumulator: 0.0,
+            timestep: TimestepConfig::default(),
+            paused: false,
+            rng_seed: rand::random(),
+            simulations: Vec::new(),
+            controls: Vec::new(),
+            schedule: Vec::new(),
+            events: EventBus::new(),
+            profiler: Profiler::default(),
+            checksums: ChecksumHistory::new(ChecksumConfig::default()),
+            replay_divergence: None,
+            recorder: None,
+            player: None,
+            rewind: None,
+        }
+    }
+
     /// Builder method to set a specific RNG seed
     pub fn with_seed(mut self, seed: u64) -> Self {
-        self.rng_seed = seed;
+        self.reseed(seed);
         self
     }
 
@@ -83,25 +282,217 @@ impl World {
         self
     }
 
+    /// Builder method to set the fixed timestep settings
+    pub fn with_timestep(mut self, timestep: TimestepConfig) -> Self {
+        self.set_timestep(timestep);
+        self
+    }
+
     /// Updates the world simulation by one tick
+    ///
+    /// The delta is scaled by the time scale and forwarded as-is. Prefer
+    /// [`World::advance`] for frame-driven updates so simulations always see
+    /// the same fixed step regardless of frame rate.
     pub fn tick(&mut self, delta_time: f32) {
+        self.apply_replay_actions();
         if self.paused {
             return;
         }
 
-        let scaled_delta = delta_time * self.time_scale;
+        self.step(delta_time * self.time_scale);
+    }
+
+    /// Advances the world by a variable frame delta using fixed-size ticks
+    ///
+    /// The scaled frame time is added to the accumulator and consumed in
+    /// `timestep.step` sized ticks. At most `timestep.max_substeps` ticks run
+    /// per call; any backlog beyond that is dropped so a slow frame can't
+    /// snowball into ever longer frames.
+    ///
+    /// Returns the interpolation alpha (0.0-1.0) between the last tick and the next.
+    pub fn advance(&mut self, frame_dt: f32) -> f32 {
+        self.apply_replay_actions();
+        if self.paused {
+            return self.interpolation_alpha();
+        }
+
+        let step = self.timestep.step;
+        self.timestep_accumulator += frame_dt.max(0.0) * self.time_scale;
+
+        let max_backlog = step * self.timestep.max_substeps as f32;
+        if self.timestep_accumulator > max_backlog {
+            debug!(
+                dropped = self.timestep_accumulator - max_backlog,
+                "Simulation falling behind, dropping backlog"
+            );
+            self.timestep_accumulator = max_backlog;
+        }
+
+        while self.timestep_accumulator >= step {
+            // Replayed actions may pause the world between ticks
+            self.apply_replay_actions();
+            if self.paused {
+                break;
+            }
+            self.consume_timestep(step);
+            self.step(step);
+        }
+
+        self.interpolation_alpha()
+    }
+
+    /// Runs a single tick of `delta_time` seconds on all active simulations
+    fn step(&mut self, delta_time: f32) {
         self.tick_count += 1;
-        self.sim_time += scaled_delta as f64;
-        self.timestep_accumulator += scaled_delta;
+        self.sim_time += delta_time as f64;
+        self.events.clear();
 
-        // Update all active simulations
-        for sim in &mut self.simulations {
-            if sim.is_active() {
-                sim.tick(scaled_delta);
+        self.run_phase(TickPhase::Pre, delta_time);
+        self.run_phase(TickPhase::Tick, delta_time);
+        self.dispatch_events();
+        self.run_phase(TickPhase::Post, delta_time);
+        self.profiler.finish_tick();
+
+        self.record_checksum();
+        self.capture_rewind_frame();
+    }
+
+    /// Returns true if the simulation at `index` should run this tick
+    fn is_running(&self, index: usize) -> bool {
+        self.simulations[index].is_active() && !self.controls[index].paused
+    }
+
+    /// Runs one phase across all running simulations in schedule order
+    fn run_phase(&mut self, phase: TickPhase, delta_time: f32) {
+        for &index in &self.schedule {
+            if self.is_running(index) {
+                let scaled = delta_time * self.controls[index].time_scale;
+                let start = Instant::now();
+                phase.run(self.simulations[index].as_mut(), scaled);
+                self.profiler.record(index, start.elapsed());
+            }
+        }
+    }
+
+    /// Collects events from every running simulation, then delivers them to all of them
+    ///
+    /// Paused simulations keep their queued events until they resume, and miss
+    /// events published while they are paused.
+    fn dispatch_events(&mut self) {
+        for &index in &self.schedule {
+            if self.is_running(index) {
+                let start = Instant::now();
+                self.simulations[index].publish_events(&mut self.events);
+                self.profiler.record(index, start.elapsed());
+            }
+        }
+
+        if self.events.is_empty() {
+            return;
+        }
+        for &index in &self.schedule {
+            if self.is_running(index) {
+                let start = Instant::now();
+                self.simulations[index].handle_events(&self.events);
+                self.profiler.record(index, start.elapsed());
             }
         }
     }
 
+    /// Returns rolling tick timings for every simulation, in registration order
+    pub fn profile(&self) -> Vec<SimulationProfile> {
+        self.simulations
+            .iter()
+            .enumerate()
+            .map(|(index, sim)| self.profiler.stats(index, sim.name()))
+            .collect()
+    }
+
+    /// Sets how many recent ticks the profile statistics cover
+    pub fn set_profile_window(&mut self, ticks: usize) {
+        self.profiler.set_window(ticks);
+    }
+
+    /// Returns the events published during the most recent tick
+    pub fn events(&self) -> &EventBus {
+        &self.events
+    }
+
+    /// Combines the world's own state and every simulation's `state_hash`
+    ///
+    /// Two worlds with the same checksum are in the same state, so comparing
+    /// checksums tick by tick finds where two runs diverge.
+    pub fn checksum(&self) -> u64 {
+        // The accumulator and pause flag depend on frame pacing, not on simulation
+        // state, so runs at different frame rates still agree
+        let mut hasher = StateHasher::new();
+        hasher.write_u64(self.tick_count);
+        hasher.write_f64(self.sim_time);
+        hasher.write_f32(self.time_scale);
+        hasher.write_u64(self.rng_seed);
+        for (sim, control) in self.simulations.iter().zip(&self.controls) {
+            hasher.write_str(sim.name());
+            hasher.write_u8(control.paused as u8);
+            hasher.write_f32(control.time_scale);
+            hasher.write_u64(sim.state_hash());
+        }
+        hasher.finish()
+    }
+
+    /// Replaces the checksum settings, discarding recorded checksums
+    pub fn set_checksum_config(&mut self, config: ChecksumConfig) {
+        self.checksums = ChecksumHistory::new(config);
+    }
+
+    /// Returns the checksums recorded so far
+    pub fn checksum_history(&self) -> &ChecksumHistory {
+        &self.checksums
+    }
+
+    /// Returns the first tick where playback diverged from the replay being played
+    ///
+    /// Stays set after playback finishes, until the next playback starts.
+    pub fn replay_divergence(&self) -> Option<u64> {
+        self.replay_divergence
+    }
+
+    /// Records the checksum for this tick and checks it against a playing replay
+    fn record_checksum(&mut self) {
+        let tick = self.tick_count;
+        let wanted = self.checksums.wants(tick);
+        let expected = self
+            .player
+            .as_ref()
+            .and_then(|player| player.expected_checksum(tick));
+        if !wanted && expected.is_none() {
+            return;
+        }
+
+        let checksum = self.checksum();
+        if wanted {
+            self.checksums.record(tick, checksum);
+            if let Some(recorder) = &mut self.recorder {
+                recorder.record_checksum(tick, checksum);
+            }
+            if self.checksums.config().log {
+                debug!(tick, checksum = %format_args!("{checksum:016x}"), "World checksum");
+            }
+        }
+
+        if let Some(expected) = expected
+            && expected != checksum
+            && self.replay_divergence.is_none()
+        {
+            warn!(
+                tick,
+                expected = %format_args!("{expected:016x}"),
+                actual = %format_args!("{checksum:016x}"),
+                "Replay diverged from the recorded session"
+            );
+            self.replay_divergence = Some(tick);
+        }
+    }
+
     /// Returns the current tick count
     pub fn tick_count(&self) -> u64 {
         self.tick_count
@@ -147,6 +538,18 @@ impl World {
         self.rng_seed
     }
 
+    /// Sets the world seed and reseeds every simulation from it
+    ///
+    /// Each simulation receives its own stream derived from the world seed and its
+    /// name (see [`rng::derive_seed`]), so the same seed reproduces the whole session
+    /// regardless of the order simulations were added in.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng_seed = seed;
+        for sim in &mut self.simulations {
+            sim.reseed(rng::derive_seed(seed, sim.name()));
+        }
+    }
+
     /// Returns the timestep accumulator value
     pub fn timestep_accumulator(&self) -> f32 {
         self.timestep_accumulator
@@ -157,9 +560,58 @@ impl World {
         self.timestep_accumulator -= timestep;
     }
 
-    /// Adds a simulation to the world
-    pub fn add_simulation(&mut self, sim: Box<dyn Simulation>) {
+    /// Returns the fixed timestep settings
+    pub fn timestep(&self) -> TimestepConfig {
+        self.timestep
+    }
+
+    /// Sets the fixed timestep settings
+    ///
+    /// The step is clamped to a small positive value and at least one substep is allowed.
+    pub fn set_timestep(&mut self, timestep: TimestepConfig) {
+        self.timestep = TimestepConfig {
+            step: timestep.step.max(1.0e-6),
+            max_substeps: timestep.max_substeps.max(1),
+        };
+    }
+
+    /// Returns how far the accumulator is between the last tick and the next (0.0-1.0)
+    ///
+    /// Renderers can use this to interpolate between the previous and current tick.
+    pub fn interpolation_alpha(&self) -> f32 {
+        (self.timestep_accumulator / self.timestep.step).clamp(0.0, 1.0)
+    }
+
+    /// Adds a simulation to the world, seeding it from the world seed
+    ///
+    /// The tick order is re-resolved from every simulation's `runs_after`; if the
+    /// new simulation would create an ordering cycle it is not added. Retained
+    /// rewind frames no longer match the world and are discarded.
+    pub fn add_simulation(&mut self, mut sim: Box<dyn Simulation>) -> Result<(), ScheduleError> {
+        sim.reseed(rng::derive_seed(self.rng_seed, sim.name()));
         self.simulations.push(sim);
+        match schedule::resolve(&self.simulations) {
+            Ok(order) => self.schedule = order,
+            Err(e) => {
+                self.simulations.pop();
+                return Err(e);
+            }
+        }
+        self.controls.push(SimulationControl::default());
+        self.profiler.add_simulation();
+
+        if let Some(rewind) = &mut self.rewind {
+            rewind.clear();
+        }
+        self.capture_rewind_frame();
+        Ok(())
+    }
+
+    /// Returns simulation names in the order they tick
+    pub fn schedule(&self) -> impl Iterator<Item = &str> {
+        self.schedule
+            .iter()
+            .map(|&index| self.simulations[index].name())
     }
 
     /// Returns a reference to all simulations
@@ -189,6 +641,36 @@ impl World {
         }
     }
 
+    /// Returns the pause state and time multiplier of a simulation
+    pub fn simulation_control(&self, name: &str) -> Option<SimulationControl> {
+        self.simulation_index(name)
+            .map(|index| self.controls[index])
+    }
+
+    /// Pauses or resumes a single simulation, returning false if no simulation has that name
+    pub fn set_simulation_paused(&mut self, name: &str, paused: bool) -> bool {
+        self.update_control(name, |control| control.paused = paused)
+    }
+
+    /// Sets a single simulation's time multiplier, returning false if no simulation has that name
+    pub fn set_simulation_time_scale(&mut self, name: &str, scale: f32) -> bool {
+        self.update_control(name, |control| control.time_scale = scale.max(0.0))
+    }
+
+    fn update_control(&mut self, name: &str, update: impl FnOnce(&mut SimulationControl)) -> bool {
+        match self.simulation_index(name) {
+            Some(index) => {
+                update(&mut self.controls[index]);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn simulation_index(&self, name: &str) -> Option<usize> {
+        self.simulations.iter().position(|sim| sim.name() == name)
+    }
+
     /// Gets a typed reference to a specific simulation
     ///
     /// # Example
@@ -208,6 +690,314 @@ impl World {
             .and_then(|s| s.as_any_mut().downcast_mut::<T>())
     }
 
+    /// Captures the complete world state, including every simulation
+    pub fn snapshot(&self) -> Result<WorldSnapshot, SnapshotError> {
+        let simulations = self
+            .simulations
+            .iter()
+            .zip(&self.controls)
+            .map(|(sim, control)| {
+                Ok(SimulationState {
+                    name: sim.name().to_string(),
+                    state: sim.save_state()?,
+                    control: *control,
+                })
+            })
+            .collect::<Result<Vec<_>, SnapshotError>>()?;
+
+        Ok(WorldSnapshot {
+            header: SnapshotHeader::current(),
+            tick_count: self.tick_count,
+            sim_time: self.sim_time,
+            time_scale: self.time_scale,
+            timestep_accumulator: self.timestep_accumulator,
+            paused: self.paused,
+            rng_seed: self.rng_seed,
+            simulations,
+        })
+    }
+
+    /// Restores world state from a snapshot
+    ///
+    /// The snapshot must contain exactly the simulations registered in this world
+    /// (matched by name). Every simulation is decoded into a copy first, so a
+    /// snapshot that fails to restore leaves the world untouched.
+    pub fn restore(&mut self, snapshot: WorldSnapshot) -> Result<(), SnapshotError> {
+        for saved in &snapshot.simulations {
+            if self.get_simulation(&saved.name).is_none() {
+                return Err(SnapshotError::UnknownSimulation(saved.name.clone()));
+            }
+        }
+        for sim in &self.simulations {
+            if !snapshot.simulations.iter().any(|s| s.name == sim.name()) {
+                return Err(SnapshotError::MissingSimulation(sim.name().to_string()));
+            }
+        }
+
+        let mut staged = Vec::with_capacity(snapshot.simulations.len());
+        for saved in snapshot.simulations {
+            if let Some(index) = self.simulation_index(&saved.name) {
+                let mut sim = self.simulations[index].clone_box();
+                sim.load_state(saved.state)?;
+                staged.push((index, sim, saved.control));
+            }
+        }
+        for (index, sim, control) in staged {
+            self.simulations[index] = sim;
+            self.controls[index] = control;
+        }
+
+        self.tick_count = snapshot.tick_count;
+        self.sim_time = snapshot.sim_time;
+        self.time_scale = snapshot.time_scale;
+        self.timestep_accumulator = snapshot.timestep_accumulator;
+        self.paused = snapshot.paused;
+        self.rng_seed = snapshot.rng_seed;
+        self.events.clear();
+        self.checksums.clear();
+
+        // Retained history belongs to the timeline we just left
+        if let Some(rewind) = &mut self.rewind {
+            rewind.clear();
+        }
+        self.capture_rewind_frame();
+
+        Ok(())
+    }
+
+    /// Saves the complete world state to a snapshot file
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        self.snapshot()?.save_to(path)
+    }
+
+    /// Restores the complete world state from a snapshot file
+    pub fn load_from(&mut self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        let snapshot = WorldSnapshot::load_from(path)?;
+        self.restore(snapshot)
+    }
+
+    /// Applies a gameplay-affecting action, recording it if a replay is being captured
+    ///
+    /// While a replay is playing only viewer controls (pause and time scale) are
+    /// accepted; everything else comes from the replay. Returns true if the action
+    /// changed the world.
+    pub fn apply(&mut self, action: WorldAction) -> bool {
+        if self.player.is_some()
+            && !matches!(
+                action,
+                WorldAction::SetPaused(_) | WorldAction::SetTimeScale(_)
+            )
+        {
+            return false;
+        }
+
+        let applied = self.apply_action(&action);
+        if applied && let Some(recorder) = &mut self.recorder {
+            recorder.record(self.tick_count, action);
+        }
+        applied
+    }
+
+    fn apply_action(&mut self, action: &WorldAction) -> bool {
+        match *action {
+            WorldAction::MakeMove { row, col } => self
+                .tictactoe_mut()
+                .is_some_and(|tictactoe| tictactoe.make_move(row, col)),
+            WorldAction::PlacePiece { row, col, tile } => self
+                .tictactoe_mut()
+                .is_some_and(|tictactoe| tictactoe.place_piece(row, col, tile)),
+            WorldAction::MakeUltimateMove { row, col } => self
+                .ultimate_mut()
+                .is_some_and(|ultimate| ultimate.make_move(row, col)),
+            WorldAction::ResetBoard => {
+                let mut reset = false;
+                if let Some(tictactoe) = self.tictactoe_mut() {
+                    tictactoe.reset();
+                    reset = true;
+                }
+                if let Some(ultimate) = self.ultimate_mut() {
+                    ultimate.reset();
+                    reset = true;
+                }
+                reset
+            }
+            WorldAction::UndoMove => self.tictactoe_mut().is_some_and(TicTacToeSimulation::undo),
+            WorldAction::RedoMove => self.tictactoe_mut().is_some_and(TicTacToeSimulation::redo),
+            WorldAction::ResetAll => {
+                self.reset_all_simulations();
+                true
+            }
+            WorldAction::SetPaused(paused) => {
+                self.paused = paused;
+                true
+            }
+            WorldAction::SetTimeScale(scale) => {
+                self.set_time_scale(scale);
+                true
+            }
+            WorldAction::SetSimulationPaused { ref name, paused } => {
+                self.set_simulation_paused(name, paused)
+            }
+            WorldAction::SetSimulationTimeScale { ref name, scale } => {
+                self.set_simulation_time_scale(name, scale)
+            }
+        }
+    }
+
+    /// Starts recording applied actions, beginning from the current world state
+    pub fn start_recording(&mut self) -> Result<(), SnapshotError> {
+        let initial = self.snapshot()?;
+        self.recorder = Some(ReplayRecorder::new(initial, self.timestep));
+        info!(tick = self.tick_count, "Replay recording started");
+        Ok(())
+    }
+
+    /// Stops recording and returns the captured replay, if one was being recorded
+    pub fn stop_recording(&mut self) -> Option<Replay> {
+        let recorder = self.recorder.take()?;
+        let replay = recorder.finish(self.tick_count);
+        info!(
+            events = replay.events.len(),
+            ticks = replay.end_tick - replay.initial.tick_count,
+            "Replay recording stopped"
+        );
+        Some(replay)
+    }
+
+    /// Returns true while a replay is being recorded
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Restores the replay's starting state and plays its actions back as the world ticks
+    ///
+    /// The world pauses once the recorded end tick is reached.
+    pub fn start_playback(&mut self, replay: Replay) -> Result<(), SnapshotError> {
+        self.restore(replay.initial.clone())?;
+        self.set_timestep(replay.timestep);
+        self.recorder = None;
+        self.replay_divergence = None;
+        info!(
+            seed = replay.seed(),
+            events = replay.events.len(),
+            "Replay playback started"
+        );
+        self.player = Some(ReplayPlayer::new(replay));
+        Ok(())
+    }
+
+    /// Stops replay playback, leaving the world in its current state
+    pub fn stop_playback(&mut self) {
+        self.player = None;
+    }
+
+    /// Returns true while a replay is playing
+    pub fn is_replaying(&self) -> bool {
+        self.player.is_some()
+    }
+
+    /// Applies replayed actions due at the current tick
+    fn apply_replay_actions(&mut self) {
+        let Some(player) = &mut self.player else {
+            return;
+        };
+
+        let due = player.take_due(self.tick_count);
+        let finished = player.is_finished(self.tick_count);
+        for action in due {
+            self.apply_action(&action);
+        }
+
+        // Hold the final recorded state instead of running past it
+        if finished {
+            info!(tick = self.tick_count, "Replay playback finished, pausing");
+            self.player = None;
+            self.paused = true;
+        }
+    }
+
+    /// Enables the rewind buffer, capturing the current state as its first frame
+    pub fn enable_rewind(&mut self, config: RewindConfig) {
+        self.rewind = Some(RewindBuffer::new(config));
+        self.capture_rewind_frame();
+    }
+
+    /// Disables the rewind buffer and frees its frames
+    pub fn disable_rewind(&mut self) {
+        self.rewind = None;
+    }
+
+    /// Returns the rewind buffer, if enabled
+    pub fn rewind_buffer(&self) -> Option<&RewindBuffer> {
+        self.rewind.as_ref()
+    }
+
+    /// Captures the current state into the rewind buffer if a frame is due
+    fn capture_rewind_frame(&mut self) {
+        let Some(rewind) = &mut self.rewind else {
+            return;
+        };
+        if !rewind.wants_frame(self.tick_count) && rewind.newest_tick().is_some() {
+            return;
+        }
+
+        rewind.push(RewindFrame::new(
+            self.tick_count,
+            self.sim_time,
+            self.timestep_accumulator,
+            self.simulations.clone(),
+            self.controls.clone(),
+        ));
+    }
+
+    /// Steps back to the newest retained frame before the current tick
+    ///
+    /// Returns false if rewind is disabled or no earlier frame is retained.
+    pub fn step_back(&mut self) -> bool {
+        let frame = self
+            .rewind
+            .as_ref()
+            .and_then(|rewind| rewind.frame_before(self.tick_count))
+            .cloned();
+        self.restore_rewind_frame(frame)
+    }
+
+    /// Jumps to the newest retained frame at or before `tick`
+    ///
+    /// The world is paused so the restored state can be inspected; ticking again
+    /// resumes from it and discards the frames that came after. Returns false if
+    /// rewind is disabled or no such frame is retained.
+    pub fn rewind_to(&mut self, tick: u64) -> bool {
+        let frame = self
+            .rewind
+            .as_ref()
+            .and_then(|rewind| rewind.frame_at_or_before(tick))
+            .cloned();
+        self.restore_rewind_frame(frame)
+    }
+
+    fn restore_rewind_frame(&mut self, frame: Option<RewindFrame>) -> bool {
+        let Some(frame) = frame else {
+            return false;
+        };
+        // Replays are keyed by tick, so jumping around would desync them
+        if self.is_recording() || self.is_replaying() {
+            warn!("Rewind is unavailable while a replay is recording or playing");
+            return false;
+        }
+
+        self.tick_count = frame.tick_count;
+        self.sim_time = frame.sim_time;
+        self.timestep_accumulator = frame.timestep_accumulator;
+        self.simulations = frame.simulations;
+        self.controls = frame.controls;
+        self.events.clear();
+        self.profiler.clear();
+        self.checksums.truncate_from(frame.tick_count + 1);
+        self.paused = true;
+        true
+    }
+
     /// Resets all simulations to their initial state
     pub fn reset_all_simulations(&mut self) {
         for sim in &mut self.simulations {
@@ -215,6 +1005,53 @@ impl World {
         }
     }
 
+    /// Lays leaf vines along the tic-tac-toe grid lines, if the leaf simulation has none
+    ///
+    /// Vines are stored in world coordinates (one unit per cell, origin at the board
+    /// center), so a 3x3 board spans -1.5..1.5. Without a tic-tac-toe simulation the
+    /// default 3x3 board is assumed.
+    pub fn init_board_vines(&mut self) {
+        // The ultimate grid grows vines along its sub-board borders only
+        let (width, height, step) = if self.ultimate().is_some() {
+            (ultimate::SIZE, ultimate::SIZE, ultimate::BLOCK)
+        } else {
+            let config = self
+                .tictactoe()
+                .map(TicTacToeSimulation::config)
+                .unwrap_or_default();
+            (config.width, config.height, 1)
+        };
+        let Some(leaf_sim) = self.leaf_mut() else {
+            return;
+        };
+        if !leaf_sim.vines().is_empty() {
+            return;
+        }
+
+        // Interior grid lines only, matching the drawn board
+        let half_width = width as f32 / 2.0;
+        let half_height = height as f32 / 2.0;
+        for i in (step..height).step_by(step) {
+            let y = i as f32 - half_height;
+            leaf_sim.add_vine_line([-half_width, y], [half_width, y]);
+        }
+        for i in (step..width).step_by(step) {
+            let x = i as f32 - half_width;
+            leaf_sim.add_vine_line([x, -half_height], [x, half_height]);
+        }
+        debug!(vines = leaf_sim.vines().len(), "Board vines initialized");
+    }
+
+    /// Convenience method to get the ultimate tic-tac-toe simulation
+    pub fn ultimate(&self) -> Option<&UltimateTicTacToeSimulation> {
+        self.get_simulation_typed::<UltimateTicTacToeSimulation>("ultimate")
+    }
+
+    /// Convenience method to get a mutable reference to the ultimate tic-tac-toe simulation
+    pub fn ultimate_mut(&mut self) -> Option<&mut UltimateTicTacToeSimulation> {
+        self.get_simulation_typed_mut::<UltimateTicTacToeSimulation>("ultimate")
+    }
+
     /// Convenience method to get the TicTacToe simulation
     pub fn tictactoe(&self) -> Option<&TicTacToeSimulation> {
         self.get_simulation_typed::<TicTacToeSimulation>("tictactoe")
@@ -237,23 +1074,506 @@ impl World {
 }
 
 impl Default for World {
+    /// Builds the world described by [`SimConfig::default`] (TicTacToe, then Leaf)
     fn default() -> Self {
-        let mut world = Self {
-            tick_count: 0,
-            sim_time: 0.0,
-            time_scale: 1.0,
-            timestep_accumulator: 0.0,
-            paused: false,
-            rng_seed: rand::random(),
-            simulations: Vec::new(),
-        };
+        SimulationRegistry::with_builtins()
+            .build_world(&SimConfig::default())
+            .expect("built-in simulations are registered")
+    }
+}
 
-        // Add TicTacToe simulation by default
-        world.add_simulation(Box::new(TicTacToeSimulation::new()));
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // Add Leaf simulation
-        world.add_simulation(Box::new(LeafSimulation::new()));
+    // Power-of-two step so frame deltas divide it exactly
+    fn test_timestep() -> TimestepConfig {
+        TimestepConfig {
+            step: 1.0 / 64.0,
+            max_substeps: 8,
+        }
+    }
 
+    fn leaf_world() -> World {
+        let mut world = World::new().with_seed(7).with_timestep(test_timestep());
+        world
+            .leaf_mut()
+            .unwrap()
+            .add_vine_line([-1.5, -0.5], [1.5, -0.5]);
         world
     }
+
+    #[test]
+    fn test_advance_runs_fixed_substeps() {
+        let mut world = World::new().with_timestep(test_timestep());
+
+        let alpha = world.advance(1.0 / 32.0 + 1.0 / 128.0);
+
+        assert_eq!(world.tick_count(), 2);
+        assert!((alpha - 0.5).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn test_advance_clamps_backlog() {
+        let mut world = World::new().with_timestep(test_timestep());
+
+        // A one second hitch would be 64 ticks; only max_substeps should run
+        world.advance(1.0);
+
+        assert_eq!(world.tick_count(), 8);
+        assert!(world.interpolation_alpha() <= 1.0);
+    }
+
+    #[test]
+    fn test_advance_respects_pause_and_time_scale() {
+        let mut world = World::new()
+            .with_timestep(test_timestep())
+            .with_paused(true);
+        world.advance(1.0 / 16.0);
+        assert_eq!(world.tick_count(), 0);
+
+        world.resume();
+        world.set_time_scale(2.0);
+        world.advance(1.0 / 32.0);
+        assert_eq!(world.tick_count(), 4);
+    }
+
+    #[test]
+    fn test_advance_is_frame_rate_independent() {
+        let mut slow = leaf_world();
+        let mut fast = leaf_world();
+
+        // Two seconds at 32 fps vs 128 fps
+        for _ in 0..64 {
+            slow.advance(1.0 / 32.0);
+        }
+        for _ in 0..256 {
+            fast.advance(1.0 / 128.0);
+        }
+
+        assert_eq!(slow.tick_count(), fast.tick_count());
+
+        let slow_leaves = slow.leaf().unwrap().leaves();
+        let fast_leaves = fast.leaf().unwrap().leaves();
+        assert!(!slow_leaves.is_empty());
+        assert_eq!(slow_leaves.len(), fast_leaves.len());
+        for (a, b) in slow_leaves.iter().zip(fast_leaves) {
+            assert_eq!(a.position, b.position);
+            assert_eq!(a.growth, b.growth);
+        }
+    }
+
+    fn run_leaves(world: &mut World) -> Vec<leaf::Leaf> {
+        if world.leaf().unwrap().vines().is_empty() {
+            world
+                .leaf_mut()
+                .unwrap()
+                .add_vine_line([-1.5, -0.5], [1.5, -0.5]);
+        }
+        for _ in 0..120 {
+            world.advance(1.0 / 64.0);
+        }
+        world.leaf().unwrap().leaves().to_vec()
+    }
+
+    #[test]
+    fn test_world_seed_reproduces_simulations() {
+        let a = run_leaves(&mut World::new().with_seed(99));
+        let b = run_leaves(&mut World::new().with_seed(99));
+        let c = run_leaves(&mut World::new().with_seed(100));
+
+        assert!(!a.is_empty());
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(&b) {
+            assert_eq!(x.position, y.position);
+            assert_eq!(x.rotation, y.rotation);
+        }
+        assert!(
+            a.iter()
+                .zip(&c)
+                .any(|(x, y)| x.position != y.position || x.rotation != y.rotation),
+            "Different world seeds should produce different leaves"
+        );
+    }
+
+    #[test]
+    fn test_seed_streams_ignore_registration_order() {
+        let mut forward = World::new().with_seed(5);
+
+        let mut reversed = World::empty().with_seed(5);
+        reversed
+            .add_simulation(Box::new(LeafSimulation::new()))
+            .unwrap();
+        reversed
+            .add_simulation(Box::new(TicTacToeSimulation::new()))
+            .unwrap();
+
+        let a = run_leaves(&mut forward);
+        let b = run_leaves(&mut reversed);
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(&b) {
+            assert_eq!(x.position, y.position);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_is_bit_exact() {
+        let mut world = leaf_world().with_time_scale(1.5);
+        world.tictactoe_mut().unwrap().make_move(1, 1);
+        for _ in 0..100 {
+            world.advance(1.0 / 60.0);
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("world.snapshot");
+        world.save_to(&path).unwrap();
+
+        let mut restored = leaf_world();
+        restored.load_from(&path).unwrap();
+
+        assert_eq!(restored.tick_count(), world.tick_count());
+        assert_eq!(restored.sim_time().to_bits(), world.sim_time().to_bits());
+        assert_eq!(restored.time_scale(), world.time_scale());
+        assert_eq!(restored.rng_seed(), world.rng_seed());
+        assert_eq!(
+            restored.tictactoe().unwrap().board(),
+            world.tictactoe().unwrap().board()
+        );
+
+        // Both worlds must keep evolving identically, including RNG draws
+        for _ in 0..100 {
+            world.advance(1.0 / 60.0);
+            restored.advance(1.0 / 60.0);
+        }
+        let expected = world.snapshot().unwrap();
+        let actual = restored.snapshot().unwrap();
+        assert_eq!(actual.simulations, expected.simulations);
+    }
+
+    #[test]
+    fn test_replay_reproduces_session() {
+        let mut live = leaf_world();
+        live.advance(0.25);
+        live.start_recording().unwrap();
+
+        live.apply(WorldAction::MakeMove { row: 0, col: 0 });
+        live.advance(0.1);
+        live.apply(WorldAction::SetTimeScale(2.0));
+        live.apply(WorldAction::MakeMove { row: 1, col: 1 });
+        live.advance(0.1);
+        live.apply(WorldAction::SetPaused(true));
+        live.advance(0.5);
+        live.apply(WorldAction::SetPaused(false));
+        live.apply(WorldAction::MakeMove { row: 2, col: 2 });
+        live.advance(0.1);
+        // Occupied cell: rejected and therefore not recorded
+        assert!(!live.apply(WorldAction::MakeMove { row: 0, col: 0 }));
+        live.advance(0.2);
+
+        let replay = live.stop_recording().unwrap();
+        assert_eq!(replay.events.len(), 6);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.replay");
+        replay.save_to(&path).unwrap();
+        let loaded = Replay::load_from(&path).unwrap();
+        assert_eq!(loaded, replay);
+
+        // Play back with different frame pacing from a fresh, differently seeded world
+        let mut playback = World::new().with_seed(1234);
+        playback.start_playback(loaded).unwrap();
+        while playback.is_replaying() {
+            playback.advance(1.0 / 30.0);
+        }
+
+        assert_eq!(playback.tick_count(), live.tick_count());
+        assert_eq!(
+            playback.snapshot().unwrap().simulations,
+            live.snapshot().unwrap().simulations
+        );
+    }
+
+    #[test]
+    fn test_playback_ignores_gameplay_input() {
+        let mut world = World::new();
+        world.start_recording().unwrap();
+        world.advance(0.1);
+        let replay = world.stop_recording().unwrap();
+
+        world.start_playback(replay).unwrap();
+        assert!(!world.apply(WorldAction::MakeMove { row: 0, col: 0 }));
+        assert!(world.apply(WorldAction::SetPaused(true)));
+    }
+
+    #[test]
+    fn test_rewind_restores_earlier_state() {
+        let mut world = leaf_world();
+        world.enable_rewind(RewindConfig::default());
+        for _ in 0..64 {
+            world.advance(1.0 / 64.0);
+        }
+        let midpoint = world.snapshot().unwrap();
+
+        world.apply(WorldAction::MakeMove { row: 1, col: 1 });
+        for _ in 0..64 {
+            world.advance(1.0 / 64.0);
+        }
+
+        assert!(world.rewind_to(64));
+        assert!(world.is_paused());
+        assert_eq!(world.tick_count(), 64);
+        assert_eq!(world.snapshot().unwrap().simulations, midpoint.simulations);
+
+        assert!(world.step_back());
+        assert_eq!(world.tick_count(), 63);
+    }
+
+    #[test]
+    fn test_resuming_after_rewind_matches_original_timeline() {
+        let mut world = leaf_world();
+        world.enable_rewind(RewindConfig::default());
+        for _ in 0..128 {
+            world.advance(1.0 / 64.0);
+        }
+        let expected = world.snapshot().unwrap();
+
+        assert!(world.rewind_to(32));
+        world.resume();
+        for _ in 0..96 {
+            world.advance(1.0 / 64.0);
+        }
+
+        assert_eq!(world.tick_count(), 128);
+        assert_eq!(world.snapshot().unwrap().simulations, expected.simulations);
+        assert_eq!(world.rewind_buffer().unwrap().newest_tick(), Some(128));
+    }
+
+    #[test]
+    fn test_rewind_buffer_respects_limits() {
+        let mut world = leaf_world();
+        world.enable_rewind(RewindConfig {
+            interval: 4,
+            max_frames: 10,
+            ..Default::default()
+        });
+        for _ in 0..200 {
+            world.advance(1.0 / 64.0);
+        }
+
+        let rewind = world.rewind_buffer().unwrap();
+        assert_eq!(rewind.len(), 10);
+        assert!(rewind.ticks().all(|tick| tick % 4 == 0));
+        assert_eq!(rewind.newest_tick(), Some(200));
+
+        let mut world = leaf_world();
+        world.enable_rewind(RewindConfig {
+            memory_budget: 1,
+            ..Default::default()
+        });
+        for _ in 0..10 {
+            world.advance(1.0 / 64.0);
+        }
+        // Budget too small for more than the newest frame
+        assert_eq!(world.rewind_buffer().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_load_rejects_foreign_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("not-a-snapshot");
+        std::fs::write(&path, b"hello world").unwrap();
+
+        let mut world = World::new();
+        assert!(matches!(
+            world.load_from(&path),
+            Err(SnapshotError::BadMagic { .. })
+        ));
+    }
+
+    #[test]
+    fn test_restore_rejects_unknown_simulation() {
+        let world = World::new();
+        let mut snapshot = world.snapshot().unwrap();
+        snapshot.simulations.push(SimulationState {
+            name: "mystery".to_string(),
+            state: serde_json::Value::Null,
+            control: SimulationControl::default(),
+        });
+
+        let mut target = World::new();
+        assert!(matches!(
+            target.restore(snapshot),
+            Err(SnapshotError::UnknownSimulation(name)) if name == "mystery"
+        ));
+    }
+
+    #[test]
+    fn test_failed_restore_leaves_world_unchanged() {
+        let mut world = leaf_world();
+        world.tictactoe_mut().unwrap().make_move(1, 1);
+        for _ in 0..30 {
+            world.advance(1.0 / 60.0);
+        }
+        let mut snapshot = world.snapshot().unwrap();
+
+        let mut target = leaf_world();
+        target.tictactoe_mut().unwrap().make_move(0, 0);
+        let before = target.checksum();
+        let tick_count = target.tick_count();
+
+        // Every simulation but the last decodes fine
+        snapshot.simulations.last_mut().unwrap().state = serde_json::json!("corrupt");
+        assert!(matches!(
+            target.restore(snapshot),
+            Err(SnapshotError::Serde(_))
+        ));
+        assert_eq!(target.checksum(), before);
+        assert_eq!(target.tick_count(), tick_count);
+        assert_eq!(
+            target.tictactoe().unwrap().board().get(0, 0),
+            Some(tictactoe::Tile::X)
+        );
+    }
+
+    #[test]
+    fn test_winning_move_grows_leaf_burst() {
+        use tictactoe::TicTacToeEvent;
+
+        let mut world = World::new().with_seed(3);
+        for (row, col) in [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)] {
+            assert!(world.apply(WorldAction::MakeMove { row, col }));
+        }
+        // Queued moves are published on the next tick
+        assert!(world.leaf().unwrap().leaves().is_empty());
+
+        world.tick(1.0 / 60.0);
+        let events = world.events().read::<TicTacToeEvent>();
+        assert_eq!(events.len(), 6);
+        assert!(matches!(
+            &events[5],
+            TicTacToeEvent::GameWon { line, .. } if *line == [(0, 0), (0, 1), (0, 2)]
+        ));
+
+        let leaves = world.leaf().unwrap().leaves();
+        assert_eq!(leaves.len(), leaf::LeafConfig::default().burst_leaves);
+        for leaf in leaves {
+            assert!((-1.0..=1.0).contains(&leaf.position[0]));
+            assert!((leaf.position[1] + 1.0).abs() <= 0.05 + 1e-6);
+        }
+
+        world.tick(1.0 / 60.0);
+        assert!(world.events().is_empty());
+    }
+
+    #[test]
+    fn test_checksums_find_first_divergence() {
+        let config = ChecksumConfig {
+            interval: 8,
+            ..Default::default()
+        };
+        let mut a = leaf_world();
+        let mut b = leaf_world();
+        a.set_checksum_config(config);
+        b.set_checksum_config(config);
+
+        for frame in 0..40 {
+            if frame == 20 {
+                b.apply(WorldAction::MakeMove { row: 1, col: 1 });
+            }
+            a.advance(1.0 / 64.0);
+            b.advance(1.0 / 64.0);
+        }
+
+        let b_history: Vec<_> = b.checksum_history().entries().collect();
+        assert_eq!(b_history.len(), 5);
+        // The move lands before tick 21; the next recorded tick is 24
+        assert_eq!(a.checksum_history().first_divergence(&b_history), Some(24));
+        assert_eq!(a.checksum_history().get(16), b.checksum_history().get(16));
+    }
+
+    #[test]
+    fn test_playback_verifies_recorded_checksums() {
+        let mut live = leaf_world();
+        live.set_checksum_config(ChecksumConfig {
+            interval: 4,
+            ..Default::default()
+        });
+        live.start_recording().unwrap();
+        live.apply(WorldAction::MakeMove { row: 0, col: 0 });
+        for _ in 0..16 {
+            live.advance(1.0 / 64.0);
+        }
+        let replay = live.stop_recording().unwrap();
+        assert_eq!(replay.checksums.len(), 4);
+
+        let mut playback = World::new();
+        playback.start_playback(replay.clone()).unwrap();
+        while playback.is_replaying() {
+            playback.advance(1.0 / 30.0);
+        }
+        assert_eq!(playback.replay_divergence(), None);
+
+        let mut tampered = replay;
+        tampered.checksums[2].checksum ^= 1;
+        let expected_tick = tampered.checksums[2].tick;
+        playback.start_playback(tampered).unwrap();
+        while playback.is_replaying() {
+            playback.advance(1.0 / 30.0);
+        }
+        assert_eq!(playback.replay_divergence(), Some(expected_tick));
+    }
+
+    #[test]
+    fn test_per_simulation_pause_and_time_scale() {
+        let mut world = leaf_world();
+        assert!(world.set_simulation_paused("leaf", true));
+        assert!(!world.set_simulation_paused("pinball", true));
+
+        for _ in 0..64 {
+            world.advance(1.0 / 64.0);
+        }
+        assert_eq!(world.tick_count(), 64);
+        assert!(world.leaf().unwrap().leaves().is_empty());
+
+        // 10x turns a 0.1s tick into a full second: two spawns at 2 leaves/s
+        assert!(world.apply(WorldAction::SetSimulationPaused {
+            name: "leaf".to_string(),
+            paused: false,
+        }));
+        world.set_simulation_time_scale("leaf", 10.0);
+        world.tick(0.1);
+        assert_eq!(world.leaf().unwrap().leaves().len(), 2);
+
+        let snapshot = world.snapshot().unwrap();
+        let mut restored = World::new();
+        restored.restore(snapshot).unwrap();
+        assert_eq!(
+            restored.simulation_control("leaf"),
+            Some(SimulationControl {
+                paused: false,
+                time_scale: 10.0,
+            })
+        );
+        assert_eq!(restored.checksum(), world.checksum());
+    }
+
+    #[test]
+    fn test_profile_tracks_running_simulations() {
+        let mut world = leaf_world();
+        world.set_profile_window(16);
+        world.set_simulation_paused("tictactoe", true);
+        for _ in 0..32 {
+            world.advance(1.0 / 64.0);
+        }
+
+        let profile = world.profile();
+        let names: Vec<_> = profile.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["tictactoe", "leaf"]);
+
+        assert_eq!(profile[0].samples, 0);
+        let leaf = &profile[1];
+        assert_eq!(leaf.samples, 16);
+        assert!(leaf.mean <= leaf.max);
+        assert!(leaf.p95 <= leaf.max);
+    }
 }
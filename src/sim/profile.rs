@@ -0,0 +1,169 @@
+//! Per-simulation tick profiling
+//!
+//! Measures the wall-clock time each simulation spends per tick (all phases plus
+//! event publishing and handling) and keeps rolling statistics over a window of
+//! recent ticks.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Default number of ticks kept for rolling statistics (4 seconds at 60 ticks per second)
+pub const DEFAULT_PROFILE_WINDOW: usize = 240;
+
+/// Rolling timing statistics for one simulation
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationProfile {
+    pub name: String,
+    /// Number of ticks in the window
+    pub samples: usize,
+    /// Time spent in the most recent tick the simulation ran
+    pub last: Duration,
+    pub mean: Duration,
+    /// 95th percentile
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+/// Collects tick timings for every simulation in a world
+#[derive(Debug, Clone)]
+pub struct Profiler {
+    window: usize,
+    /// Recent timings per simulation, indexed like the world's simulations
+    samples: Vec<VecDeque<Duration>>,
+    /// Time accumulated by each simulation during the current tick, if it ran
+    current: Vec<Option<Duration>>,
+}
+
+impl Profiler {
+    /// Creates a profiler keeping `window` ticks per simulation
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            samples: Vec::new(),
+            current: Vec::new(),
+        }
+    }
+
+    /// Returns the number of ticks kept per simulation
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Changes the window size, dropping the oldest samples if it shrinks
+    pub fn set_window(&mut self, window: usize) {
+        self.window = window.max(1);
+        for samples in &mut self.samples {
+            while samples.len() > self.window {
+                samples.pop_front();
+            }
+        }
+    }
+
+    /// Adds a slot for a newly registered simulation
+    pub fn add_simulation(&mut self) {
+        self.samples.push(VecDeque::new());
+        self.current.push(None);
+    }
+
+    /// Adds time spent by the simulation at `index` during the current tick
+    pub fn record(&mut self, index: usize, elapsed: Duration) {
+        let current = &mut self.current[index];
+        *current = Some(current.unwrap_or_default() + elapsed);
+    }
+
+    /// Closes the current tick, pushing a sample for every simulation that ran
+    pub fn finish_tick(&mut self) {
+        for (samples, current) in self.samples.iter_mut().zip(&mut self.current) {
+            if let Some(elapsed) = current.take() {
+                if samples.len() == self.window {
+                    samples.pop_front();
+                }
+                samples.push_back(elapsed);
+            }
+        }
+    }
+
+    /// Returns rolling statistics for the simulation at `index`
+    pub fn stats(&self, index: usize, name: &str) -> SimulationProfile {
+        let samples = &self.samples[index];
+        let mut sorted: Vec<Duration> = samples.iter().copied().collect();
+        sorted.sort_unstable();
+
+        let count = sorted.len();
+        let total: Duration = sorted.iter().sum();
+        let p95 = match count {
+            0 => Duration::ZERO,
+            _ => sorted[(count * 95).div_ceil(100) - 1],
+        };
+
+        SimulationProfile {
+            name: name.to_string(),
+            samples: count,
+            last: samples.back().copied().unwrap_or_default(),
+            mean: total.checked_div(count as u32).unwrap_or_default(),
+            p95,
+            max: sorted.last().copied().unwrap_or_default(),
+        }
+    }
+
+    /// Drops every recorded sample
+    pub fn clear(&mut self) {
+        for samples in &mut self.samples {
+            samples.clear();
+        }
+        self.current.fill(None);
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new(DEFAULT_PROFILE_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(value: u64) -> Duration {
+        Duration::from_micros(value)
+    }
+
+    #[test]
+    fn test_rolling_statistics() {
+        let mut profiler = Profiler::new(100);
+        profiler.add_simulation();
+        for value in 1..=100 {
+            profiler.record(0, micros(value));
+            profiler.finish_tick();
+        }
+
+        let stats = profiler.stats(0, "sim");
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.last, micros(100));
+        assert_eq!(stats.mean, Duration::from_nanos(50_500));
+        assert_eq!(stats.p95, micros(95));
+        assert_eq!(stats.max, micros(100));
+    }
+
+    #[test]
+    fn test_window_drops_oldest_and_skips_idle_ticks() {
+        let mut profiler = Profiler::new(3);
+        profiler.add_simulation();
+        profiler.add_simulation();
+        for value in [50, 1, 2, 3] {
+            // Phases of one tick add up
+            profiler.record(0, micros(value));
+            profiler.record(0, micros(value));
+            profiler.finish_tick();
+        }
+
+        let busy = profiler.stats(0, "busy");
+        assert_eq!(busy.samples, 3);
+        assert_eq!(busy.max, micros(6));
+
+        let idle = profiler.stats(1, "idle");
+        assert_eq!(idle.samples, 0);
+        assert_eq!(idle.mean, Duration::ZERO);
+    }
+}
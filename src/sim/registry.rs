@@ -0,0 +1,314 @@
+//! Simulation registry and config-driven world composition
+//!
+//! Maps simulation kinds to factory functions so the `[sim]` section of the profile
+//! config can choose which simulations a world contains, in what order, and with
+//! what parameters.
+//!
+//! # Example
+//!
+//! ```toml
+//! [sim]
+//! seed = 1234
+//!
+//! [[sim.simulations]]
+//! kind = "tictactoe"
+//! [sim.simulations.params]
+//! width = 15
+//! height = 15
+//! win_length = 5
+//! variant = "standard"
+//!
+//! [[sim.simulations]]
+//! kind = "leaf"
+//! [sim.simulations.params]
+//! spawn_rate = 4.0
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::leaf::LeafConfig;
+use super::tictactoe::TicTacToeParams;
+use super::{
+    ChecksumConfig, LeafSimulation, ScheduleError, Simulation, TicTacToeSimulation, TimestepConfig,
+    UltimateTicTacToeSimulation, World,
+};
+
+/// Parameters passed to a simulation factory (the `params` table from config)
+pub type SimulationParams = serde_json::Value;
+
+/// Creates a simulation from its parameters
+pub type SimulationFactory = fn(&SimulationParams) -> Result<Box<dyn Simulation>, RegistryError>;
+
+/// Errors that can occur while building simulations from config
+#[derive(Debug, thiserror::Error)]
+pub enum RegistryError {
+    #[error("unknown simulation kind '{0}'")]
+    UnknownKind(String),
+    #[error("invalid parameters for simulation '{kind}': {source}")]
+    InvalidParams {
+        kind: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("simulation '{0}' is listed more than once")]
+    Duplicate(String),
+    #[error(transparent)]
+    Schedule(#[from] ScheduleError),
+}
+
+/// A single entry in `[[sim.simulations]]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulationEntry {
+    /// Registered simulation kind (e.g. "tictactoe", "leaf")
+    pub kind: String,
+    /// Kind-specific parameters; missing fields use the simulation's defaults
+    #[serde(default)]
+    pub params: SimulationParams,
+}
+
+impl SimulationEntry {
+    /// Creates an entry with default parameters
+    pub fn new(kind: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            params: SimulationParams::Null,
+        }
+    }
+}
+
+/// `[sim]` section of the profile config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimConfig {
+    /// World seed; a random seed is chosen when unset
+    pub seed: Option<u64>,
+    /// Initial time scale multiplier
+    pub time_scale: f32,
+    /// Fixed timestep settings
+    pub timestep: TimestepConfig,
+    /// Checksum recording and logging settings
+    pub checksums: ChecksumConfig,
+    /// Simulations to create, in order
+    pub simulations: Vec<SimulationEntry>,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            time_scale: 1.0,
+            timestep: TimestepConfig::default(),
+            checksums: ChecksumConfig::default(),
+            simulations: vec![
+                SimulationEntry::new("tictactoe"),
+                SimulationEntry::new("leaf"),
+            ],
+        }
+    }
+}
+
+/// Maps simulation kinds to factories
+#[derive(Debug, Clone, Default)]
+pub struct SimulationRegistry {
+    factories: BTreeMap<String, SimulationFactory>,
+}
+
+impl SimulationRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry containing all built-in simulations
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register("tictactoe", |params| {
+            let params: TicTacToeParams = parse_params("tictactoe", params)?;
+            let mut game = TicTacToeSimulation::with_variant(params.board, params.variant)
+                .with_reset_delay(params.reset_delay);
+            if let Some(clock) = params.clock {
+                game = game.with_clock(clock);
+            }
+            Ok(Box::new(game))
+        });
+        registry.register("ultimate", |_| {
+            Ok(Box::new(UltimateTicTacToeSimulation::new()))
+        });
+        registry.register("leaf", |params| {
+            let config: LeafConfig = parse_params("leaf", params)?;
+            Ok(Box::new(LeafSimulation::with_config(config)))
+        });
+        registry
+    }
+
+    /// Registers a factory for `kind`, replacing any existing one
+    pub fn register(&mut self, kind: impl Into<String>, factory: SimulationFactory) {
+        self.factories.insert(kind.into(), factory);
+    }
+
+    /// Returns the registered kinds in alphabetical order
+    pub fn kinds(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+
+    /// Creates a simulation of the given kind
+    pub fn create(
+        &self,
+        kind: &str,
+        params: &SimulationParams,
+    ) -> Result<Box<dyn Simulation>, RegistryError> {
+        let factory = self
+            .factories
+            .get(kind)
+            .ok_or_else(|| RegistryError::UnknownKind(kind.to_string()))?;
+        factory(params)
+    }
+
+    /// Builds a world from the `[sim]` config section
+    pub fn build_world(&self, config: &SimConfig) -> Result<World, RegistryError> {
+        let mut world = World::empty()
+            .with_time_scale(config.time_scale)
+            .with_timestep(config.timestep);
+        world.set_checksum_config(config.checksums);
+        if let Some(seed) = config.seed {
+            world.reseed(seed);
+        }
+
+        for entry in &config.simulations {
+            let sim = self.create(&entry.kind, &entry.params)?;
+            if world.get_simulation(sim.name()).is_some() {
+                return Err(RegistryError::Duplicate(sim.name().to_string()));
+            }
+            world.add_simulation(sim)?;
+        }
+
+        Ok(world)
+    }
+}
+
+/// Deserializes factory parameters, using defaults when none were given
+pub fn parse_params<T: DeserializeOwned + Default>(
+    kind: &str,
+    params: &SimulationParams,
+) -> Result<T, RegistryError> {
+    if params.is_null() {
+        return Ok(T::default());
+    }
+
+    T::deserialize(params).map_err(|source| RegistryError::InvalidParams {
+        kind: kind.to_string(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtins_build_default_world() {
+        let world = SimulationRegistry::with_builtins()
+            .build_world(&SimConfig::default())
+            .unwrap();
+
+        let names: Vec<_> = world.simulations().iter().map(|s| s.name()).collect();
+        assert_eq!(names, ["tictactoe", "leaf"]);
+    }
+
+    #[test]
+    fn test_config_selects_order_and_params() {
+        let config = SimConfig {
+            seed: Some(42),
+            time_scale: 2.0,
+            simulations: vec![
+                SimulationEntry {
+                    kind: "leaf".to_string(),
+                    params: serde_json::json!({ "spawn_rate": 8.0, "max_leaves": 3 }),
+                },
+                SimulationEntry::new("tictactoe"),
+            ],
+            ..Default::default()
+        };
+
+        let mut world = SimulationRegistry::with_builtins()
+            .build_world(&config)
+            .unwrap();
+        assert_eq!(world.rng_seed(), 42);
+        assert_eq!(world.time_scale(), 2.0);
+        assert_eq!(world.simulations()[0].name(), "leaf");
+
+        world
+            .leaf_mut()
+            .unwrap()
+            .add_vine_line([0.0, 0.0], [1.0, 0.0]);
+        world.tick(1.0);
+        assert_eq!(world.leaf().unwrap().leaves().len(), 3);
+    }
+
+    #[test]
+    fn test_tictactoe_params_size_board_and_vines() {
+        let config = SimConfig {
+            simulations: vec![
+                SimulationEntry {
+                    kind: "tictactoe".to_string(),
+                    params: serde_json::json!({ "width": 7, "height": 6, "win_length": 4 }),
+                },
+                SimulationEntry::new("leaf"),
+            ],
+            ..Default::default()
+        };
+
+        let mut world = SimulationRegistry::with_builtins()
+            .build_world(&config)
+            .unwrap();
+        let game = world.tictactoe().unwrap();
+        assert_eq!((game.board().width(), game.board().height()), (7, 6));
+        assert_eq!(game.win_length(), 4);
+        assert_eq!(game.cell_center(0, 6), [3.0, -2.5]);
+
+        // 5 horizontal and 6 vertical interior grid lines
+        world.init_board_vines();
+        let vines = world.leaf().unwrap().vines();
+        assert_eq!(vines.len(), 11);
+        assert_eq!(vines[0].start(), [-3.5, -2.0]);
+        assert_eq!(vines[10].end(), [2.5, 3.0]);
+    }
+
+    #[test]
+    fn test_build_world_reports_bad_config() {
+        let registry = SimulationRegistry::with_builtins();
+
+        let unknown = SimConfig {
+            simulations: vec![SimulationEntry::new("pinball")],
+            ..Default::default()
+        };
+        assert!(matches!(
+            registry.build_world(&unknown),
+            Err(RegistryError::UnknownKind(kind)) if kind == "pinball"
+        ));
+
+        let duplicate = SimConfig {
+            simulations: vec![SimulationEntry::new("leaf"), SimulationEntry::new("leaf")],
+            ..Default::default()
+        };
+        assert!(matches!(
+            registry.build_world(&duplicate),
+            Err(RegistryError::Duplicate(_))
+        ));
+
+        let invalid = SimConfig {
+            simulations: vec![SimulationEntry {
+                kind: "leaf".to_string(),
+                params: serde_json::json!({ "spawn_rate": "fast" }),
+            }],
+            ..Default::default()
+        };
+        assert!(matches!(
+            registry.build_world(&invalid),
+            Err(RegistryError::InvalidParams { .. })
+        ));
+    }
+}
@@ -0,0 +1,183 @@
+//! Action replays
+//!
+//! Records every gameplay-affecting [`WorldAction`] stamped with the world tick it was
+//! applied at, together with a snapshot of the world when recording started. Playing
+//! a replay restores that snapshot and re-applies each action before the same tick,
+//! reproducing the session exactly.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::TimestepConfig;
+use super::checksum::{self, TickChecksum};
+use super::snapshot::{self, SnapshotError, WorldSnapshot};
+use super::tictactoe::Tile;
+
+/// Magic bytes identifying a replay file
+pub const REPLAY_MAGIC: [u8; 4] = *b"OILR";
+
+/// Current replay format version
+pub const REPLAY_VERSION: u32 = 1;
+
+/// A gameplay-affecting action applied to the world
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WorldAction {
+    /// Place the current player's piece on the tic-tac-toe board
+    MakeMove { row: usize, col: usize },
+    /// Place a chosen piece for the current player, in variants that allow a choice
+    PlacePiece { row: usize, col: usize, tile: Tile },
+    /// Place the current player's piece on the ultimate tic-tac-toe grid
+    MakeUltimateMove { row: usize, col: usize },
+    /// Clear the tic-tac-toe boards, keeping scores
+    ResetBoard,
+    /// Take back the last tic-tac-toe move
+    UndoMove,
+    /// Replay the last undone tic-tac-toe move
+    RedoMove,
+    /// Reset every simulation
+    ResetAll,
+    /// Pause or resume the world
+    SetPaused(bool),
+    /// Change the world time scale
+    SetTimeScale(f32),
+    /// Pause or resume a single simulation
+    SetSimulationPaused { name: String, paused: bool },
+    /// Change a single simulation's time multiplier
+    SetSimulationTimeScale { name: String, scale: f32 },
+}
+
+/// An action stamped with the tick it was applied before
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    /// World tick count at the moment the action was applied
+    pub tick: u64,
+    pub action: WorldAction,
+}
+
+/// A recorded session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    /// World state when recording started (includes the world seed and build info)
+    pub initial: WorldSnapshot,
+    /// Fixed timestep the session was recorded with
+    pub timestep: TimestepConfig,
+    /// Recorded actions in the order they were applied
+    pub events: Vec<ReplayEvent>,
+    /// Tick count when recording stopped
+    pub end_tick: u64,
+    /// World checksums captured while recording, used to detect playback desyncs
+    #[serde(default)]
+    pub checksums: Vec<TickChecksum>,
+}
+
+impl Replay {
+    /// Returns the world seed the session was recorded with
+    pub fn seed(&self) -> u64 {
+        self.initial.rng_seed
+    }
+
+    /// Writes the replay to a file, replacing it if it exists
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        let file = File::create(path)?;
+        snapshot::write_container(BufWriter::new(file), REPLAY_MAGIC, REPLAY_VERSION, self)
+    }
+
+    /// Reads a replay from a file
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        let file = File::open(path)?;
+        snapshot::read_container(BufReader::new(file), REPLAY_MAGIC, REPLAY_VERSION)
+    }
+}
+
+/// Collects actions while a session is being recorded
+#[derive(Debug, Clone)]
+pub struct ReplayRecorder {
+    initial: WorldSnapshot,
+    timestep: TimestepConfig,
+    events: Vec<ReplayEvent>,
+    checksums: Vec<TickChecksum>,
+}
+
+impl ReplayRecorder {
+    /// Starts a recording from the given world state
+    pub fn new(initial: WorldSnapshot, timestep: TimestepConfig) -> Self {
+        Self {
+            initial,
+            timestep,
+            events: Vec::new(),
+            checksums: Vec::new(),
+        }
+    }
+
+    /// Records an action applied at `tick`
+    pub fn record(&mut self, tick: u64, action: WorldAction) {
+        self.events.push(ReplayEvent { tick, action });
+    }
+
+    /// Records the world checksum at `tick`
+    pub fn record_checksum(&mut self, tick: u64, checksum: u64) {
+        self.checksums.push(TickChecksum { tick, checksum });
+    }
+
+    /// Returns the actions recorded so far
+    pub fn events(&self) -> &[ReplayEvent] {
+        &self.events
+    }
+
+    /// Finishes the recording at `end_tick`
+    pub fn finish(self, end_tick: u64) -> Replay {
+        Replay {
+            initial: self.initial,
+            timestep: self.timestep,
+            events: self.events,
+            end_tick,
+            checksums: self.checksums,
+        }
+    }
+}
+
+/// Feeds recorded actions back at the ticks they were recorded at
+#[derive(Debug, Clone)]
+pub struct ReplayPlayer {
+    replay: Replay,
+    next_event: usize,
+}
+
+impl ReplayPlayer {
+    /// Creates a player positioned at the start of the replay
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next_event: 0,
+        }
+    }
+
+    /// Returns the replay being played
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Removes and returns every action due at or before `tick`, in recorded order
+    pub fn take_due(&mut self, tick: u64) -> Vec<WorldAction> {
+        let due = self.replay.events[self.next_event..]
+            .iter()
+            .take_while(|event| event.tick <= tick)
+            .map(|event| event.action.clone())
+            .collect::<Vec<_>>();
+        self.next_event += due.len();
+        due
+    }
+
+    /// Returns the checksum recorded at `tick`, if one was captured
+    pub fn expected_checksum(&self, tick: u64) -> Option<u64> {
+        checksum::lookup(self.replay.checksums.iter().copied(), tick)
+    }
+
+    /// Returns true once every action has been applied and `tick` reached the end
+    pub fn is_finished(&self, tick: u64) -> bool {
+        self.next_event >= self.replay.events.len() && tick >= self.replay.end_tick
+    }
+}
@@ -0,0 +1,186 @@
+//! Rewind / time-travel buffer
+//!
+//! Keeps a bounded ring buffer of in-memory world frames so the world can step
+//! backwards or jump to any retained tick. Frames hold cloned simulations, and the
+//! buffer evicts the oldest frames once either the frame limit or the memory budget
+//! is exceeded.
+
+use std::collections::VecDeque;
+
+use super::{Simulation, SimulationControl};
+
+/// Rewind buffer settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RewindConfig {
+    /// Capture a frame every `interval` ticks (1 = every tick)
+    pub interval: u64,
+    /// Maximum number of frames retained
+    pub max_frames: usize,
+    /// Approximate upper bound on memory used by retained frames, in bytes
+    pub memory_budget: usize,
+}
+
+impl Default for RewindConfig {
+    fn default() -> Self {
+        Self {
+            interval: 1,
+            max_frames: 600,                 // 10 seconds at 60 ticks per second
+            memory_budget: 64 * 1024 * 1024, // 64 MiB
+        }
+    }
+}
+
+/// World state captured at a single tick
+#[derive(Clone)]
+pub struct RewindFrame {
+    pub tick_count: u64,
+    pub sim_time: f64,
+    pub timestep_accumulator: f32,
+    pub simulations: Vec<Box<dyn Simulation>>,
+    pub controls: Vec<SimulationControl>,
+    /// Approximate memory used by this frame, in bytes
+    pub size_bytes: usize,
+}
+
+impl RewindFrame {
+    /// Creates a frame, measuring its footprint from the captured simulations
+    pub fn new(
+        tick_count: u64,
+        sim_time: f64,
+        timestep_accumulator: f32,
+        simulations: Vec<Box<dyn Simulation>>,
+        controls: Vec<SimulationControl>,
+    ) -> Self {
+        let size_bytes = std::mem::size_of::<Self>()
+            + controls.len() * std::mem::size_of::<SimulationControl>()
+            + simulations
+                .iter()
+                .map(|sim| sim.memory_footprint())
+                .sum::<usize>();
+
+        Self {
+            tick_count,
+            sim_time,
+            timestep_accumulator,
+            simulations,
+            controls,
+            size_bytes,
+        }
+    }
+}
+
+/// Bounded ring buffer of world frames, ordered by tick
+pub struct RewindBuffer {
+    config: RewindConfig,
+    frames: VecDeque<RewindFrame>,
+    memory_usage: usize,
+}
+
+impl RewindBuffer {
+    /// Creates an empty buffer
+    pub fn new(config: RewindConfig) -> Self {
+        Self {
+            config: RewindConfig {
+                interval: config.interval.max(1),
+                ..config
+            },
+            frames: VecDeque::new(),
+            memory_usage: 0,
+        }
+    }
+
+    /// Returns the buffer settings
+    pub fn config(&self) -> RewindConfig {
+        self.config
+    }
+
+    /// Returns true if a frame should be captured at `tick`
+    pub fn wants_frame(&self, tick: u64) -> bool {
+        tick.is_multiple_of(self.config.interval)
+    }
+
+    /// Adds a frame, discarding any frames at or after its tick and evicting the
+    /// oldest frames until the buffer fits its limits
+    ///
+    /// Discarding later frames means resuming from a rewound tick starts a new timeline.
+    pub fn push(&mut self, frame: RewindFrame) {
+        while self
+            .frames
+            .back()
+            .is_some_and(|last| last.tick_count >= frame.tick_count)
+        {
+            self.pop_back();
+        }
+
+        self.memory_usage += frame.size_bytes;
+        self.frames.push_back(frame);
+
+        // Always keep the newest frame, even if it alone exceeds the budget
+        while self.frames.len() > 1
+            && (self.frames.len() > self.config.max_frames
+                || self.memory_usage > self.config.memory_budget)
+        {
+            if let Some(evicted) = self.frames.pop_front() {
+                self.memory_usage -= evicted.size_bytes;
+            }
+        }
+    }
+
+    fn pop_back(&mut self) {
+        if let Some(removed) = self.frames.pop_back() {
+            self.memory_usage -= removed.size_bytes;
+        }
+    }
+
+    /// Returns the newest frame at or before `tick`
+    pub fn frame_at_or_before(&self, tick: u64) -> Option<&RewindFrame> {
+        self.frames
+            .iter()
+            .rev()
+            .find(|frame| frame.tick_count <= tick)
+    }
+
+    /// Returns the newest frame strictly before `tick`
+    pub fn frame_before(&self, tick: u64) -> Option<&RewindFrame> {
+        self.frames
+            .iter()
+            .rev()
+            .find(|frame| frame.tick_count < tick)
+    }
+
+    /// Returns the oldest retained tick
+    pub fn oldest_tick(&self) -> Option<u64> {
+        self.frames.front().map(|frame| frame.tick_count)
+    }
+
+    /// Returns the newest retained tick
+    pub fn newest_tick(&self) -> Option<u64> {
+        self.frames.back().map(|frame| frame.tick_count)
+    }
+
+    /// Returns the ticks of all retained frames, oldest first
+    pub fn ticks(&self) -> impl Iterator<Item = u64> + '_ {
+        self.frames.iter().map(|frame| frame.tick_count)
+    }
+
+    /// Returns the number of retained frames
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns true if no frames are retained
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns the approximate memory used by retained frames, in bytes
+    pub fn memory_usage(&self) -> usize {
+        self.memory_usage
+    }
+
+    /// Drops every retained frame
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.memory_usage = 0;
+    }
+}
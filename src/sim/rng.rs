@@ -0,0 +1,39 @@
+//! Deterministic seed derivation
+//!
+//! A single world seed is split into independent, named RNG streams. Each stream's
+//! seed depends only on its parent seed and its name, so streams stay stable when
+//! simulations are added, removed, or reordered.
+
+/// FNV-1a 64-bit offset basis
+pub(crate) const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64-bit prime
+pub(crate) const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Derives the seed of the stream called `name` from a parent seed
+///
+/// # Example
+/// ```
+/// use oil_pool::sim::rng::derive_seed;
+///
+/// let leaf = derive_seed(1234, "leaf");
+/// assert_eq!(leaf, derive_seed(1234, "leaf"));
+/// assert_ne!(leaf, derive_seed(1234, "tictactoe"));
+/// ```
+pub fn derive_seed(parent: u64, name: &str) -> u64 {
+    let mut name_hash = FNV_OFFSET;
+    for byte in name.bytes() {
+        name_hash ^= byte as u64;
+        name_hash = name_hash.wrapping_mul(FNV_PRIME);
+    }
+
+    splitmix64(parent ^ splitmix64(name_hash))
+}
+
+/// SplitMix64 finalizer - scrambles a 64-bit value so nearby inputs give unrelated outputs
+pub fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
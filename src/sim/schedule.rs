@@ -0,0 +1,311 @@
+//! Tick scheduling
+//!
+//! Resolves the "runs after" constraints declared by simulations (see
+//! [`Simulation::runs_after`]) into the order the world ticks them in. Simulations
+//! with no constraint between them keep their insertion order.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::{DiGraph, NodeIndex};
+use tracing::debug;
+
+use super::Simulation;
+
+/// Phases of a world tick
+///
+/// Every active simulation finishes a phase before any simulation starts the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TickPhase {
+    Pre,
+    Tick,
+    Post,
+}
+
+impl TickPhase {
+    /// All phases in the order they run
+    pub const ALL: [TickPhase; 3] = [TickPhase::Pre, TickPhase::Tick, TickPhase::Post];
+
+    /// Runs this phase of `sim`
+    pub fn run(self, sim: &mut dyn Simulation, delta_time: f32) {
+        match self {
+            TickPhase::Pre => sim.pre_tick(delta_time),
+            TickPhase::Tick => sim.tick(delta_time),
+            TickPhase::Post => sim.post_tick(delta_time),
+        }
+    }
+}
+
+/// Errors that can occur while resolving the tick order
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ScheduleError {
+    /// Simulations that must each run after the previous one, ending where it started
+    #[error("simulation ordering has a cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// Returns simulation indices in tick order
+///
+/// Constraints naming a simulation that isn't present are ignored, so a simulation
+/// can declare an optional dependency.
+pub fn resolve(simulations: &[Box<dyn Simulation>]) -> Result<Vec<usize>, ScheduleError> {
+    // Edges point from a simulation to the simulations that run after it
+    let mut graph = DiGraph::<(), ()>::with_capacity(simulations.len(), 0);
+    for _ in simulations {
+        graph.add_node(());
+    }
+
+    for (index, sim) in simulations.iter().enumerate() {
+        for &dependency in sim.runs_after() {
+            match simulations
+                .iter()
+                .position(|other| other.name() == dependency)
+            {
+                Some(before) => {
+                    graph.update_edge(NodeIndex::new(before), NodeIndex::new(index), ());
+                }
+                None => debug!(
+                    simulation = sim.name(),
+                    dependency, "Ordering constraint names a missing simulation, ignoring"
+                ),
+            }
+        }
+    }
+
+    // Kahn's algorithm, always picking the earliest-inserted ready simulation
+    let mut pending: Vec<usize> = graph
+        .node_indices()
+        .map(|node| {
+            graph
+                .neighbors_directed(node, petgraph::Direction::Incoming)
+                .count()
+        })
+        .collect();
+    let mut ready: BinaryHeap<Reverse<usize>> = pending
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count == 0)
+        .map(|(index, _)| Reverse(index))
+        .collect();
+
+    let mut order = Vec::with_capacity(simulations.len());
+    while let Some(Reverse(index)) = ready.pop() {
+        order.push(index);
+        for next in graph.neighbors(NodeIndex::new(index)) {
+            let next = next.index();
+            pending[next] -= 1;
+            if pending[next] == 0 {
+                ready.push(Reverse(next));
+            }
+        }
+    }
+
+    if order.len() < simulations.len() {
+        return Err(find_cycle(&graph, simulations));
+    }
+
+    Ok(order)
+}
+
+/// Describes one cycle in a graph that failed to sort
+fn find_cycle(graph: &DiGraph<(), ()>, simulations: &[Box<dyn Simulation>]) -> ScheduleError {
+    let component = tarjan_scc(graph)
+        .into_iter()
+        .find(|component| component.len() > 1 || graph.contains_edge(component[0], component[0]))
+        .expect("an unsortable graph contains a cycle");
+
+    // Walk edges inside the component until a node repeats
+    let start = *component.iter().min().expect("components are never empty");
+    let mut path = vec![start];
+    loop {
+        let current = *path.last().expect("path starts non-empty");
+        let next = graph
+            .neighbors(current)
+            .filter(|node| component.contains(node))
+            .min()
+            .expect("every node in a cycle has a successor inside it");
+        if let Some(repeat) = path.iter().position(|&node| node == next) {
+            path.drain(..repeat);
+            path.push(next);
+            break;
+        }
+        path.push(next);
+    }
+
+    ScheduleError::Cycle(
+        path.into_iter()
+            .map(|node| simulations[node.index()].name().to_string())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::sim::World;
+
+    /// Records each phase it runs into a shared log
+    #[derive(Clone)]
+    struct Probe {
+        name: &'static str,
+        after: Vec<&'static str>,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Probe {
+        fn boxed(
+            name: &'static str,
+            after: &[&'static str],
+            log: &Rc<RefCell<Vec<String>>>,
+        ) -> Box<dyn Simulation> {
+            Box::new(Self {
+                name,
+                after: after.to_vec(),
+                log: Rc::clone(log),
+            })
+        }
+    }
+
+    impl Simulation for Probe {
+        fn pre_tick(&mut self, _delta_time: f32) {
+            self.log.borrow_mut().push(format!("pre {}", self.name));
+        }
+
+        fn tick(&mut self, _delta_time: f32) {
+            self.log.borrow_mut().push(format!("tick {}", self.name));
+        }
+
+        fn post_tick(&mut self, _delta_time: f32) {
+            self.log.borrow_mut().push(format!("post {}", self.name));
+        }
+
+        fn reset(&mut self) {}
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn runs_after(&self) -> &[&str] {
+            &self.after
+        }
+
+        fn save_state(&self) -> Result<serde_json::Value, serde_json::Error> {
+            Ok(serde_json::Value::Null)
+        }
+
+        fn load_state(&mut self, _state: serde_json::Value) -> Result<(), serde_json::Error> {
+            Ok(())
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    fn names(simulations: &[Box<dyn Simulation>], order: &[usize]) -> Vec<String> {
+        order
+            .iter()
+            .map(|&index| simulations[index].name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_unconstrained_keeps_insertion_order() {
+        let log = Rc::default();
+        let sims = vec![
+            Probe::boxed("c", &[], &log),
+            Probe::boxed("a", &[], &log),
+            Probe::boxed("b", &[], &log),
+        ];
+
+        assert_eq!(resolve(&sims).unwrap(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_runs_after_reorders() {
+        let log = Rc::default();
+        let sims = vec![
+            Probe::boxed("reactor", &["board"], &log),
+            Probe::boxed("other", &[], &log),
+            Probe::boxed("board", &["missing"], &log),
+        ];
+
+        let order = resolve(&sims).unwrap();
+        assert_eq!(names(&sims, &order), ["other", "board", "reactor"]);
+    }
+
+    #[test]
+    fn test_cycle_is_reported() {
+        let log = Rc::default();
+        let sims = vec![
+            Probe::boxed("free", &[], &log),
+            Probe::boxed("a", &["c"], &log),
+            Probe::boxed("b", &["a"], &log),
+            Probe::boxed("c", &["b"], &log),
+        ];
+
+        let err = resolve(&sims).unwrap_err();
+        assert_eq!(
+            err,
+            ScheduleError::Cycle(vec!["a".into(), "b".into(), "c".into(), "a".into()])
+        );
+        assert_eq!(
+            err.to_string(),
+            "simulation ordering has a cycle: a -> b -> c -> a"
+        );
+
+        let selfish = vec![Probe::boxed("a", &["a"], &log)];
+        assert!(resolve(&selfish).is_err());
+    }
+
+    #[test]
+    fn test_world_runs_phases_in_schedule_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut world = World::empty();
+        world
+            .add_simulation(Probe::boxed("reactor", &["board"], &log))
+            .unwrap();
+        world
+            .add_simulation(Probe::boxed("board", &[], &log))
+            .unwrap();
+        assert_eq!(world.schedule().collect::<Vec<_>>(), ["board", "reactor"]);
+
+        world.tick(0.1);
+        assert_eq!(
+            *log.borrow(),
+            [
+                "pre board",
+                "pre reactor",
+                "tick board",
+                "tick reactor",
+                "post board",
+                "post reactor"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_world_rejects_cycle() {
+        let log = Rc::default();
+        let mut world = World::empty();
+        world
+            .add_simulation(Probe::boxed("a", &["b"], &log))
+            .unwrap();
+
+        let err = world
+            .add_simulation(Probe::boxed("b", &["a"], &log))
+            .unwrap_err();
+        assert!(matches!(err, ScheduleError::Cycle(_)));
+        assert_eq!(world.simulations().len(), 1);
+        assert_eq!(world.schedule().collect::<Vec<_>>(), ["a"]);
+    }
+}
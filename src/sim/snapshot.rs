@@ -0,0 +1,160 @@
+//! World snapshots
+//!
+//! Saves the complete world state (world clock, seed, and every simulation's internal
+//! state) to a versioned, zstd-compressed file and restores it bit-exact.
+//!
+//! # File layout
+//!
+//! ```text
+//! [magic: 4 bytes][format version: u32 LE][zstd-compressed JSON payload]
+//! ```
+//!
+//! The payload starts with a [`SnapshotHeader`] describing the build that wrote it.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::SimulationControl;
+use crate::build_info;
+
+/// Magic bytes identifying a world snapshot file
+pub const SNAPSHOT_MAGIC: [u8; 4] = *b"OILS";
+
+/// Current snapshot format version
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// zstd compression level used for container files
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Errors that can occur while saving or loading snapshot files
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to encode or decode state: {0}")]
+    Serde(#[from] serde_json::Error),
+    #[error("not a valid file (expected magic {expected:?}, found {found:?})")]
+    BadMagic { expected: [u8; 4], found: [u8; 4] },
+    #[error("unsupported format version {found} (this build reads version {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+    #[error("snapshot contains simulation '{0}' which is not registered in this world")]
+    UnknownSimulation(String),
+    #[error("simulation '{0}' is missing from the snapshot")]
+    MissingSimulation(String),
+}
+
+/// Build information stored at the start of every snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotHeader {
+    /// Format version of the payload
+    pub format_version: u32,
+    /// Build version string (`branch@sha`)
+    pub build_version: String,
+    /// Full git commit SHA
+    pub git_sha: String,
+    /// When the writing binary was built
+    pub build_timestamp: String,
+    /// Compiler version used for the writing binary
+    pub rustc_version: String,
+}
+
+impl SnapshotHeader {
+    /// Creates a header describing the currently running build
+    pub fn current() -> Self {
+        Self {
+            format_version: SNAPSHOT_VERSION,
+            build_version: build_info::version_string(),
+            git_sha: build_info::GIT_SHA.to_string(),
+            build_timestamp: build_info::BUILD_TIMESTAMP.to_string(),
+            rustc_version: build_info::RUSTC_SEMVER.to_string(),
+        }
+    }
+}
+
+/// Serialized internal state of a single simulation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulationState {
+    /// Simulation name as returned by `Simulation::name`
+    pub name: String,
+    /// State produced by `Simulation::save_state`
+    pub state: serde_json::Value,
+    /// Per-simulation pause state and time multiplier
+    #[serde(default)]
+    pub control: SimulationControl,
+}
+
+/// Complete world state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    pub header: SnapshotHeader,
+    pub tick_count: u64,
+    pub sim_time: f64,
+    pub time_scale: f32,
+    pub timestep_accumulator: f32,
+    pub paused: bool,
+    pub rng_seed: u64,
+    pub simulations: Vec<SimulationState>,
+}
+
+impl WorldSnapshot {
+    /// Writes the snapshot to a file, replacing it if it exists
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        let file = File::create(path)?;
+        write_container(BufWriter::new(file), SNAPSHOT_MAGIC, SNAPSHOT_VERSION, self)
+    }
+
+    /// Reads a snapshot from a file
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        let file = File::open(path)?;
+        read_container(BufReader::new(file), SNAPSHOT_MAGIC, SNAPSHOT_VERSION)
+    }
+}
+
+/// Writes `value` as a versioned, zstd-compressed container
+pub(crate) fn write_container<W: Write, T: Serialize>(
+    mut writer: W,
+    magic: [u8; 4],
+    version: u32,
+    value: &T,
+) -> Result<(), SnapshotError> {
+    writer.write_all(&magic)?;
+    writer.write_all(&version.to_le_bytes())?;
+
+    let mut encoder = zstd::stream::Encoder::new(writer, COMPRESSION_LEVEL)?;
+    serde_json::to_writer(&mut encoder, value)?;
+    encoder.finish()?.flush()?;
+    Ok(())
+}
+
+/// Reads a container written by [`write_container`], validating magic and version
+pub(crate) fn read_container<R: Read, T: DeserializeOwned>(
+    mut reader: R,
+    magic: [u8; 4],
+    version: u32,
+) -> Result<T, SnapshotError> {
+    let mut found = [0u8; 4];
+    reader.read_exact(&mut found)?;
+    if found != magic {
+        return Err(SnapshotError::BadMagic {
+            expected: magic,
+            found,
+        });
+    }
+
+    let mut version_bytes = [0u8; 4];
+    reader.read_exact(&mut version_bytes)?;
+    let found_version = u32::from_le_bytes(version_bytes);
+    if found_version != version {
+        return Err(SnapshotError::UnsupportedVersion {
+            found: found_version,
+            supported: version,
+        });
+    }
+
+    let decoder = zstd::stream::Decoder::new(reader)?;
+    Ok(serde_json::from_reader(decoder)?)
+}
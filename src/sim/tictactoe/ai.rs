@@ -0,0 +1,479 @@
+//! Computer opponent for tic-tac-toe
+//!
+//! Searches the game tree with alpha-beta minimax under the game's [`Rules`], so it
+//! plays every variant. Difficulty controls how often the engine's choice is
+//! replaced by a random legal move. On boards larger than 3x3 the search is cut off
+//! after a few moves and only considers cells near existing pieces, so play is
+//! strong but no longer perfect.
+
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use super::rules::{Outcome, Rules, Standard};
+use super::{Board, GameState, Player, TicTacToeSimulation, Tile};
+
+/// Score of a won position before subtracting the moves it took, so faster wins
+/// score higher and slower losses score less badly
+const WIN_SCORE: i32 = 1_000_000;
+
+/// Moves are only searched within this many cells of an existing piece
+const CANDIDATE_RADIUS: usize = 2;
+
+/// Deepest search on boards larger than 3x3, where deeper searches stall the
+/// event loop for a visible moment
+const LARGE_BOARD_DEPTH: u32 = 3;
+
+/// How well the computer plays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    /// Always plays a random legal move
+    Random,
+    /// Takes immediate wins and blocks immediate threats, otherwise plays randomly
+    Greedy,
+    /// Plays perfectly, except for occasional random blunders
+    Easy,
+    /// Never loses
+    Perfect,
+}
+
+/// Computer opponent settings (`[ai]` config section)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AiConfig {
+    /// Whether the computer plays at all
+    pub enabled: bool,
+    /// Which side the computer plays
+    pub player: Player,
+    pub difficulty: Difficulty,
+    /// Chance of a random move on `Easy` (0.0-1.0)
+    pub blunder_chance: f32,
+    /// Seconds to wait before moving
+    pub move_delay: f32,
+    /// Moves to search ahead; 9 solves 3x3, larger boards are capped at 3 to stay responsive
+    pub max_depth: u32,
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            player: Player::O,
+            difficulty: Difficulty::Perfect,
+            blunder_chance: 0.3,
+            move_delay: 0.4,
+            max_depth: 9,
+        }
+    }
+}
+
+/// Computer player for one side of the board
+#[derive(Debug, Clone)]
+pub struct TicTacToeAi {
+    player: Player,
+    difficulty: Difficulty,
+    blunder_chance: f32,
+    max_depth: u32,
+    rng: ChaCha12Rng,
+}
+
+impl TicTacToeAi {
+    /// Creates an opponent; `seed` drives its random and blunder moves
+    pub fn new(config: &AiConfig, seed: u64) -> Self {
+        Self {
+            player: config.player,
+            difficulty: config.difficulty,
+            blunder_chance: config.blunder_chance.clamp(0.0, 1.0),
+            max_depth: config.max_depth.max(1),
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the side the computer plays
+    pub fn player(&self) -> Player {
+        self.player
+    }
+
+    /// Returns the difficulty level
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Returns true if the game is waiting for this opponent's move
+    pub fn is_turn(&self, game: &TicTacToeSimulation) -> bool {
+        game.game_state() == GameState::Playing && game.current_player() == self.player
+    }
+
+    /// Picks the next move as (row, col, piece), or None if it isn't this
+    /// opponent's turn
+    pub fn choose_move(&mut self, game: &TicTacToeSimulation) -> Option<(usize, usize, Tile)> {
+        if !self.is_turn(game) {
+            return None;
+        }
+
+        let board = game.board();
+        let rules = game.rules();
+        let blunder = match self.difficulty {
+            Difficulty::Random => true,
+            Difficulty::Greedy => return self.greedy_move(game),
+            Difficulty::Easy => self.rng.random::<f32>() < self.blunder_chance,
+            Difficulty::Perfect => false,
+        };
+        if blunder {
+            let moves: Vec<_> = board.empty_cells().collect();
+            let (row, col) = moves.choose(&mut self.rng).copied()?;
+            let tile = rules.pieces(self.player).choose(&mut self.rng).copied()?;
+            Some((row, col, tile))
+        } else {
+            let depth = if board.width() * board.height() > 9 {
+                self.max_depth.min(LARGE_BOARD_DEPTH)
+            } else {
+                self.max_depth
+            };
+            best_move_with(rules, board, game.win_length(), self.player, depth)
+        }
+    }
+
+    /// Picks a winning move if there is one, else blocks a cell where the opponent
+    /// would win next, else a random move that doesn't lose on the spot
+    fn greedy_move(&mut self, game: &TicTacToeSimulation) -> Option<(usize, usize, Tile)> {
+        let rules = game.rules();
+        let player = self.player;
+        let mut board = game.board().clone();
+        let mut winner_after = |row, col, mover: Player, tile| {
+            board.set(row, col, tile);
+            let outcome = rules.outcome(&board, game.win_length(), row, col, mover);
+            board.set(row, col, Tile::Empty);
+            match outcome {
+                Outcome::Won { winner, .. } => Some(winner),
+                Outcome::Continue | Outcome::Draw => None,
+            }
+        };
+
+        let cells: Vec<_> = game.board().empty_cells().collect();
+        let moves: Vec<_> = cells
+            .iter()
+            .flat_map(|&(row, col)| {
+                rules
+                    .pieces(player)
+                    .iter()
+                    .map(move |&tile| (row, col, tile))
+            })
+            .collect();
+        if let Some(&win) = moves
+            .iter()
+            .find(|&&(row, col, tile)| winner_after(row, col, player, tile) == Some(player))
+        {
+            return Some(win);
+        }
+
+        let opponent = player.opponent();
+        let safe: Vec<_> = moves
+            .iter()
+            .copied()
+            .filter(|&(row, col, tile)| winner_after(row, col, player, tile) != Some(opponent))
+            .collect();
+        let threat = cells.iter().find(|&&(row, col)| {
+            rules
+                .pieces(opponent)
+                .iter()
+                .any(|&tile| winner_after(row, col, opponent, tile) == Some(opponent))
+        });
+        if let Some(&block) =
+            threat.and_then(|&(row, col)| safe.iter().find(|&&(r, c, _)| (r, c) == (row, col)))
+        {
+            return Some(block);
+        }
+
+        let choices = if safe.is_empty() { &moves } else { &safe };
+        choices.choose(&mut self.rng).copied()
+    }
+}
+
+/// Returns the strongest move for `player` under standard rules found searching
+/// `max_depth` moves ahead, preferring the first in row-major order among equally
+/// good moves
+pub fn best_move(
+    board: &Board,
+    win_length: usize,
+    player: Player,
+    max_depth: u32,
+) -> Option<(usize, usize)> {
+    best_move_with(&Standard, board, win_length, player, max_depth).map(|(row, col, _)| (row, col))
+}
+
+/// Returns the strongest move for `player` under `rules` as (row, col, piece),
+/// preferring the first cell in row-major order and the player's usual piece among
+/// equally good moves
+pub fn best_move_with(
+    rules: &dyn Rules,
+    board: &Board,
+    win_length: usize,
+    player: Player,
+    max_depth: u32,
+) -> Option<(usize, usize, Tile)> {
+    let mut search = Search {
+        rules,
+        board: board.clone(),
+        win_length,
+        max_depth,
+    };
+    let mut best = None;
+    let mut alpha = -WIN_SCORE - 1;
+    for (row, col) in search.candidates() {
+        for &tile in rules.pieces(player) {
+            search.board.set(row, col, tile);
+            let score = -search.negamax((row, col), player.opponent(), 1, -WIN_SCORE - 1, -alpha);
+            search.board.set(row, col, Tile::Empty);
+
+            if score > alpha {
+                alpha = score;
+                best = Some((row, col, tile));
+            }
+        }
+    }
+    best
+}
+
+/// Alpha-beta search state
+struct Search<'a> {
+    rules: &'a dyn Rules,
+    board: Board,
+    win_length: usize,
+    max_depth: u32,
+}
+
+impl Search<'_> {
+    /// Scores the position for `to_move`, who plays next, `depth` moves into the
+    /// search after the opponent played `last`
+    fn negamax(
+        &mut self,
+        last: (usize, usize),
+        to_move: Player,
+        depth: u32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        let outcome = self.rules.outcome(
+            &self.board,
+            self.win_length,
+            last.0,
+            last.1,
+            to_move.opponent(),
+        );
+        match outcome {
+            Outcome::Won { winner, .. } if winner == to_move => return WIN_SCORE - depth as i32,
+            Outcome::Won { .. } => return -(WIN_SCORE - depth as i32),
+            Outcome::Draw => return 0,
+            Outcome::Continue if depth >= self.max_depth => return 0,
+            Outcome::Continue => {}
+        }
+
+        for (row, col) in self.candidates() {
+            for &tile in self.rules.pieces(to_move) {
+                self.board.set(row, col, tile);
+                let score = -self.negamax((row, col), to_move.opponent(), depth + 1, -beta, -alpha);
+                self.board.set(row, col, Tile::Empty);
+
+                alpha = alpha.max(score);
+                if alpha >= beta {
+                    return alpha;
+                }
+            }
+        }
+        alpha
+    }
+
+    /// Returns the empty cells worth searching in row-major order: those near an
+    /// existing piece, or the center of an empty board
+    fn candidates(&self) -> Vec<(usize, usize)> {
+        let board = &self.board;
+        if board.is_empty() {
+            return vec![(board.height() / 2, board.width() / 2)];
+        }
+
+        board
+            .empty_cells()
+            .filter(|&(row, col)| {
+                let rows = row.saturating_sub(CANDIDATE_RADIUS)..=row + CANDIDATE_RADIUS;
+                rows.into_iter().any(|r| {
+                    let cols = col.saturating_sub(CANDIDATE_RADIUS)..=col + CANDIDATE_RADIUS;
+                    cols.into_iter()
+                        .any(|c| board.get(r, c).is_some_and(|tile| tile != Tile::Empty))
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::tictactoe::{BoardConfig, Variant};
+
+    fn ai(player: Player, difficulty: Difficulty, seed: u64) -> TicTacToeAi {
+        let config = AiConfig {
+            player,
+            difficulty,
+            ..Default::default()
+        };
+        TicTacToeAi::new(&config, seed)
+    }
+
+    fn play(moves: &[(usize, usize)]) -> TicTacToeSimulation {
+        let mut game = TicTacToeSimulation::new();
+        for &(row, col) in moves {
+            assert!(game.make_move(row, col));
+        }
+        game
+    }
+
+    /// Plays a full game and returns the final state
+    fn play_out(x: &mut TicTacToeAi, o: &mut TicTacToeAi) -> GameState {
+        play_out_variant(Variant::Standard, x, o)
+    }
+
+    fn play_out_variant(variant: Variant, x: &mut TicTacToeAi, o: &mut TicTacToeAi) -> GameState {
+        let mut game = TicTacToeSimulation::with_variant(BoardConfig::default(), variant);
+        while game.game_state() == GameState::Playing {
+            let mover = match game.current_player() {
+                Player::X => &mut *x,
+                Player::O => &mut *o,
+            };
+            let (row, col, tile) = mover.choose_move(&game).expect("a move while playing");
+            assert!(game.place_piece(row, col, tile));
+        }
+        game.game_state()
+    }
+
+    #[test]
+    fn test_takes_win_over_block() {
+        // X: (0,0) (0,1)   O: (1,0) (1,1)   X to move can win at (0,2)
+        let game = play(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert_eq!(best_move(game.board(), 3, Player::X, 9), Some((0, 2)));
+    }
+
+    #[test]
+    fn test_blocks_immediate_threat() {
+        // X threatens (0,2); O must block
+        let game = play(&[(0, 0), (1, 1), (0, 1)]);
+        let mut o = ai(Player::O, Difficulty::Perfect, 0);
+        assert_eq!(o.choose_move(&game), Some((0, 2, Tile::O)));
+    }
+
+    #[test]
+    fn test_shallow_search_on_large_board() {
+        // 9x9 four-in-a-row: X has (4,1)..(4,3) and O already holds (4,0)
+        let mut game = TicTacToeSimulation::with_config(BoardConfig {
+            width: 9,
+            height: 9,
+            win_length: 4,
+        });
+        for (row, col) in [(4, 1), (4, 0), (4, 2), (8, 8), (4, 3)] {
+            assert!(game.make_move(row, col));
+        }
+        assert_eq!(best_move(game.board(), 4, Player::O, 2), Some((4, 4)));
+
+        // Given the move instead, X completes the line
+        assert_eq!(best_move(game.board(), 4, Player::X, 2), Some((4, 4)));
+    }
+
+    #[test]
+    fn test_default_depth_is_capped_on_large_boards() {
+        let mut game = TicTacToeSimulation::with_config(BoardConfig {
+            width: 5,
+            height: 5,
+            win_length: 4,
+        });
+        for (row, col) in [(2, 2), (1, 1), (2, 3)] {
+            assert!(game.make_move(row, col));
+        }
+        let mut o = ai(Player::O, Difficulty::Perfect, 0);
+        assert_eq!(
+            o.choose_move(&game),
+            best_move_with(&Standard, game.board(), 4, Player::O, LARGE_BOARD_DEPTH)
+        );
+    }
+
+    #[test]
+    fn test_search_follows_variant_rules() {
+        // Perfect play: the first player wins notakto, misère is a draw
+        let mut x = ai(Player::X, Difficulty::Perfect, 0);
+        let mut o = ai(Player::O, Difficulty::Perfect, 0);
+        assert_eq!(
+            play_out_variant(Variant::Notakto, &mut x, &mut o),
+            GameState::Won(Player::X)
+        );
+        assert_eq!(
+            play_out_variant(Variant::Misere, &mut x, &mut o),
+            GameState::Draw
+        );
+
+        // In wild, X wins by finishing O's line
+        let mut board = Board::new(3, 3);
+        board.set(0, 0, Tile::O);
+        board.set(0, 1, Tile::O);
+        board.set(1, 1, Tile::X);
+        assert_eq!(
+            best_move_with(Variant::Wild.rules(), &board, 3, Player::X, 9),
+            Some((0, 2, Tile::O))
+        );
+    }
+
+    #[test]
+    fn test_greedy_takes_wins_then_blocks() {
+        let mut x = ai(Player::X, Difficulty::Greedy, 0);
+        // X: (0,0) (0,1)   O: (1,0) (1,1)   X wins rather than blocking
+        let game = play(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert_eq!(x.choose_move(&game), Some((0, 2, Tile::X)));
+
+        // X threatens (0,2); O blocks
+        let game = play(&[(0, 0), (1, 1), (0, 1)]);
+        let mut o = ai(Player::O, Difficulty::Greedy, 0);
+        assert_eq!(o.choose_move(&game), Some((0, 2, Tile::O)));
+
+        // In misère, X avoids completing its own top row
+        let mut game = TicTacToeSimulation::with_variant(BoardConfig::default(), Variant::Misere);
+        for (row, col) in [(0, 0), (1, 0), (0, 1), (2, 2)] {
+            assert!(game.make_move(row, col));
+        }
+        for seed in 0..20 {
+            let mut x = ai(Player::X, Difficulty::Greedy, seed);
+            let (row, col, _) = x.choose_move(&game).unwrap();
+            assert_ne!((row, col), (0, 2), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_waits_for_its_turn() {
+        let game = TicTacToeSimulation::new();
+        let mut o = ai(Player::O, Difficulty::Perfect, 0);
+        assert_eq!(o.choose_move(&game), None);
+    }
+
+    #[test]
+    fn test_perfect_play_draws_and_never_loses() {
+        let mut x = ai(Player::X, Difficulty::Perfect, 0);
+        let mut o = ai(Player::O, Difficulty::Perfect, 0);
+        assert_eq!(play_out(&mut x, &mut o), GameState::Draw);
+
+        for seed in 0..20 {
+            let mut random = ai(Player::X, Difficulty::Random, seed);
+            let state = play_out(&mut random, &mut o);
+            assert_ne!(state, GameState::Won(Player::X), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_random_moves_are_seeded() {
+        let game = play(&[(1, 1)]);
+        let first = ai(Player::O, Difficulty::Random, 9).choose_move(&game);
+        let again = ai(Player::O, Difficulty::Random, 9).choose_move(&game);
+        assert_eq!(first, again);
+        let (row, col, tile) = first.unwrap();
+        assert_eq!(tile, Tile::O);
+        assert_eq!(game.board().get(row, col), Some(Tile::Empty));
+    }
+}
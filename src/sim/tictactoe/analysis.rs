@@ -0,0 +1,217 @@
+//! Perfect-play analysis of tic-tac-toe positions
+//!
+//! Solves the game tree under the game's [`Rules`] to find, for each empty cell,
+//! what playing there leads to if both sides play perfectly afterwards. Positions
+//! are memoized, which keeps a full 3x3 solve to a few thousand positions, but the
+//! tree still grows exponentially, so only positions with at most
+//! [`MAX_EMPTY_CELLS`] empty cells are analyzed.
+
+use std::collections::HashMap;
+
+use super::rules::{Outcome, Rules};
+use super::{Board, GameState, Player, TicTacToeSimulation, Tile};
+
+/// Largest number of empty cells a position may have and still be analyzed
+pub const MAX_EMPTY_CELLS: usize = 10;
+
+/// Game-theoretic result of a move for the player making it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Verdict {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Verdict {
+    /// Returns the same result seen from the other side
+    fn flip(self) -> Self {
+        match self {
+            Verdict::Win => Verdict::Loss,
+            Verdict::Draw => Verdict::Draw,
+            Verdict::Loss => Verdict::Win,
+        }
+    }
+}
+
+/// Result of playing an empty cell, assuming perfect play from then on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellAnalysis {
+    pub row: usize,
+    pub col: usize,
+    /// Best piece to place there, which only matters in variants with a choice
+    pub tile: Tile,
+    /// Result for the side to move
+    pub verdict: Verdict,
+    /// Moves until the game ends, counting this one: the winner hurries, the loser
+    /// holds out
+    pub distance: u32,
+}
+
+impl TicTacToeSimulation {
+    /// Analyzes every empty cell for the side to move, in row-major order
+    ///
+    /// Returns an empty list once the game is over, and None if the position has
+    /// more than [`MAX_EMPTY_CELLS`] empty cells.
+    pub fn analyze(&self) -> Option<Vec<CellAnalysis>> {
+        if self.game_state != GameState::Playing {
+            return Some(Vec::new());
+        }
+        if self.board.empty_cells().count() > MAX_EMPTY_CELLS {
+            return None;
+        }
+
+        let mut solver = Solver {
+            rules: self.rules(),
+            board: self.board.clone(),
+            win_length: self.win_length,
+            memo: HashMap::new(),
+        };
+        let player = self.current_player;
+        let cells: Vec<_> = self.board.empty_cells().collect();
+        Some(
+            cells
+                .into_iter()
+                .map(|(row, col)| {
+                    let (tile, (verdict, distance)) = self
+                        .rules()
+                        .pieces(player)
+                        .iter()
+                        .map(|&tile| (tile, solver.play(row, col, tile, player)))
+                        .max_by_key(|&(_, result)| rank(result))
+                        .expect("every player has a piece");
+                    CellAnalysis {
+                        row,
+                        col,
+                        tile,
+                        verdict,
+                        distance,
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Orders results from best to worst for the player who gets them: quick wins,
+/// slow wins, draws, slow losses, quick losses
+fn rank((verdict, distance): (Verdict, u32)) -> (u8, i64) {
+    match verdict {
+        Verdict::Win => (2, -(distance as i64)),
+        Verdict::Draw => (1, 0),
+        Verdict::Loss => (0, distance as i64),
+    }
+}
+
+/// Memoized perfect-play search
+struct Solver<'a> {
+    rules: &'a dyn Rules,
+    board: Board,
+    win_length: usize,
+    /// Best result for the side to move in positions already solved
+    memo: HashMap<(Board, Player), (Verdict, u32)>,
+}
+
+impl Solver<'_> {
+    /// Returns the result for `player` of placing `tile` at (row, col)
+    fn play(&mut self, row: usize, col: usize, tile: Tile, player: Player) -> (Verdict, u32) {
+        self.board.set(row, col, tile);
+        let outcome = self
+            .rules
+            .outcome(&self.board, self.win_length, row, col, player);
+        let result = match outcome {
+            Outcome::Won { winner, .. } if winner == player => (Verdict::Win, 1),
+            Outcome::Won { .. } => (Verdict::Loss, 1),
+            Outcome::Draw => (Verdict::Draw, 1),
+            Outcome::Continue => {
+                let (verdict, distance) = self.solve(player.opponent());
+                (verdict.flip(), distance + 1)
+            }
+        };
+        self.board.set(row, col, Tile::Empty);
+        result
+    }
+
+    /// Returns the best result for `to_move` in the current, unfinished position
+    fn solve(&mut self, to_move: Player) -> (Verdict, u32) {
+        let key = (self.board.clone(), to_move);
+        if let Some(&result) = self.memo.get(&key) {
+            return result;
+        }
+
+        let rules = self.rules;
+        let cells: Vec<_> = self.board.empty_cells().collect();
+        let result = cells
+            .into_iter()
+            .flat_map(|cell| rules.pieces(to_move).iter().map(move |&tile| (cell, tile)))
+            .map(|((row, col), tile)| self.play(row, col, tile, to_move))
+            .max_by_key(|&result| rank(result))
+            .expect("an unfinished position has an empty cell");
+        self.memo.insert(key, result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::tictactoe::{BoardConfig, Variant};
+
+    fn play(game: &mut TicTacToeSimulation, moves: &[(usize, usize)]) {
+        for &(row, col) in moves {
+            assert!(game.make_move(row, col));
+        }
+    }
+
+    fn cell(analysis: &[CellAnalysis], row: usize, col: usize) -> CellAnalysis {
+        *analysis
+            .iter()
+            .find(|cell| (cell.row, cell.col) == (row, col))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_empty_board_is_a_draw_everywhere() {
+        let analysis = TicTacToeSimulation::new().analyze().unwrap();
+        assert_eq!(analysis.len(), 9);
+        assert!(analysis.iter().all(|cell| cell.verdict == Verdict::Draw));
+        assert!(analysis.iter().all(|cell| cell.distance == 9));
+    }
+
+    #[test]
+    fn test_mistakes_show_as_losses() {
+        // X in the center; O's edge replies lose, corner replies draw
+        let mut game = TicTacToeSimulation::new();
+        play(&mut game, &[(1, 1)]);
+        let analysis = game.analyze().unwrap();
+        assert_eq!(cell(&analysis, 0, 0).verdict, Verdict::Draw);
+        assert_eq!(cell(&analysis, 0, 1).verdict, Verdict::Loss);
+
+        // X: (0,0) (0,1)   O: (1,0) (1,1)   X wins at (0,2); elsewhere O takes (1,2)
+        let mut game = TicTacToeSimulation::new();
+        play(&mut game, &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        let analysis = game.analyze().unwrap();
+        let now = cell(&analysis, 0, 2);
+        assert_eq!((now.verdict, now.distance), (Verdict::Win, 1));
+        let loses = cell(&analysis, 2, 0);
+        assert_eq!((loses.verdict, loses.distance), (Verdict::Loss, 2));
+    }
+
+    #[test]
+    fn test_analysis_follows_variant_and_size_limits() {
+        // Notakto: the first player wins from the center
+        let game = TicTacToeSimulation::with_variant(BoardConfig::default(), Variant::Notakto);
+        let analysis = game.analyze().unwrap();
+        assert_eq!(cell(&analysis, 1, 1).verdict, Verdict::Win);
+
+        let large = TicTacToeSimulation::with_config(BoardConfig {
+            width: 4,
+            height: 4,
+            win_length: 3,
+        });
+        assert_eq!(large.analyze(), None);
+
+        let mut over = TicTacToeSimulation::new();
+        play(&mut over, &[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]);
+        assert_eq!(over.analyze(), Some(Vec::new()));
+    }
+}
@@ -0,0 +1,275 @@
+//! m,n,k game board
+//!
+//! A `width` x `height` grid where a player wins by placing `win_length` pieces in
+//! a row, column or diagonal. Wins are found by scanning outwards from the last
+//! move, so checking a move costs O(win_length) whatever the board size.
+
+use serde::{Deserialize, Serialize};
+
+use super::Tile;
+
+/// Directions a line can run in as (row, col) steps: across, down and both diagonals
+const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// Board dimensions and win length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BoardConfig {
+    /// Number of columns
+    pub width: usize,
+    /// Number of rows
+    pub height: usize,
+    /// Pieces in a row needed to win
+    pub win_length: usize,
+}
+
+impl BoardConfig {
+    /// Returns the config with dimensions of at least one cell and a win length that
+    /// fits on the board
+    pub fn clamped(self) -> Self {
+        let width = self.width.max(1);
+        let height = self.height.max(1);
+        Self {
+            width,
+            height,
+            win_length: self.win_length.clamp(1, width.max(height)),
+        }
+    }
+
+    /// Returns the world-space center of a board cell
+    ///
+    /// World units are one cell wide with the origin at the board center, matching
+    /// the coordinates vines are laid out in.
+    pub fn cell_center(&self, row: usize, col: usize) -> [f32; 2] {
+        [
+            col as f32 - (self.width as f32 - 1.0) / 2.0,
+            row as f32 - (self.height as f32 - 1.0) / 2.0,
+        ]
+    }
+}
+
+impl Default for BoardConfig {
+    fn default() -> Self {
+        Self {
+            width: 3,
+            height: 3,
+            win_length: 3,
+        }
+    }
+}
+
+/// Grid of tiles, stored row-major
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Board {
+    width: usize,
+    height: usize,
+    tiles: Vec<Tile>,
+}
+
+impl Board {
+    /// Creates an empty board
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            tiles: vec![Tile::Empty; width * height],
+        }
+    }
+
+    /// Returns the number of columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns true if (row, col) is on the board
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row < self.height && col < self.width
+    }
+
+    /// Returns the tile at (row, col), or None if it is off the board
+    pub fn get(&self, row: usize, col: usize) -> Option<Tile> {
+        self.contains(row, col)
+            .then(|| self.tiles[row * self.width + col])
+    }
+
+    /// Sets the tile at (row, col)
+    ///
+    /// # Panics
+    /// Panics if (row, col) is off the board.
+    pub fn set(&mut self, row: usize, col: usize, tile: Tile) {
+        assert!(
+            self.contains(row, col),
+            "cell ({row}, {col}) is off the board"
+        );
+        self.tiles[row * self.width + col] = tile;
+    }
+
+    /// Returns each row of tiles, top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[Tile]> {
+        self.tiles.chunks(self.width.max(1))
+    }
+
+    /// Returns the empty cells as (row, col) in row-major order
+    pub fn empty_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| **tile == Tile::Empty)
+            .map(|(index, _)| (index / self.width, index % self.width))
+    }
+
+    /// Returns true if no tile is empty
+    pub fn is_full(&self) -> bool {
+        self.tiles.iter().all(|&tile| tile != Tile::Empty)
+    }
+
+    /// Returns true if every tile is empty
+    pub fn is_empty(&self) -> bool {
+        self.tiles.iter().all(|&tile| tile == Tile::Empty)
+    }
+
+    /// Empties every tile
+    pub fn clear(&mut self) {
+        self.tiles.fill(Tile::Empty);
+    }
+
+    /// Returns the longest run of at least `win_length` matching pieces through
+    /// (row, col), as cells from one end to the other
+    ///
+    /// Runs longer than `win_length` are returned whole.
+    pub fn line_through(
+        &self,
+        row: usize,
+        col: usize,
+        win_length: usize,
+    ) -> Option<Vec<(usize, usize)>> {
+        let tile = self.get(row, col).filter(|&tile| tile != Tile::Empty)?;
+
+        // Reversed so that `max_by_key`, which keeps the last of equal runs, prefers
+        // rows, then columns, then diagonals
+        DIRECTIONS
+            .iter()
+            .rev()
+            .map(|&(d_row, d_col)| {
+                let back = self.run_length(row, col, -d_row, -d_col, tile);
+                let forward = self.run_length(row, col, d_row, d_col, tile);
+                let start = (
+                    row.wrapping_add_signed(-d_row * back as isize),
+                    col.wrapping_add_signed(-d_col * back as isize),
+                );
+                (0..=back + forward)
+                    .map(|step| {
+                        (
+                            start.0.wrapping_add_signed(d_row * step as isize),
+                            start.1.wrapping_add_signed(d_col * step as isize),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|line| line.len() >= win_length)
+            .max_by_key(|line| line.len())
+    }
+
+    /// Counts matching tiles after (row, col) in one direction, not including it
+    fn run_length(&self, row: usize, col: usize, d_row: isize, d_col: isize, tile: Tile) -> usize {
+        let mut count = 0;
+        let (mut r, mut c) = (row, col);
+        loop {
+            let (Some(next_r), Some(next_c)) =
+                (r.checked_add_signed(d_row), c.checked_add_signed(d_col))
+            else {
+                return count;
+            };
+            if self.get(next_r, next_c) != Some(tile) {
+                return count;
+            }
+            (r, c) = (next_r, next_c);
+            count += 1;
+        }
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        let config = BoardConfig::default();
+        Self::new(config.width, config.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place(board: &mut Board, tile: Tile, cells: &[(usize, usize)]) {
+        for &(row, col) in cells {
+            board.set(row, col, tile);
+        }
+    }
+
+    #[test]
+    fn test_line_through_finds_runs_in_every_direction() {
+        let mut board = Board::new(15, 15);
+        place(
+            &mut board,
+            Tile::X,
+            &[(7, 3), (7, 4), (7, 5), (7, 6), (7, 7)],
+        );
+        assert_eq!(
+            board.line_through(7, 5, 5),
+            Some(vec![(7, 3), (7, 4), (7, 5), (7, 6), (7, 7)])
+        );
+        assert_eq!(board.line_through(7, 5, 6), None);
+
+        // Anti-diagonal running into the left edge
+        place(
+            &mut board,
+            Tile::O,
+            &[(0, 4), (1, 3), (2, 2), (3, 1), (4, 0)],
+        );
+        assert_eq!(
+            board.line_through(4, 0, 5),
+            Some(vec![(0, 4), (1, 3), (2, 2), (3, 1), (4, 0)])
+        );
+
+        // Gaps and other players' pieces break a run
+        board.set(7, 8, Tile::O);
+        place(&mut board, Tile::X, &[(7, 9), (7, 10)]);
+        assert_eq!(board.line_through(7, 9, 3), None);
+    }
+
+    #[test]
+    fn test_overlines_are_returned_whole() {
+        let mut board = Board::new(7, 6);
+        place(
+            &mut board,
+            Tile::X,
+            &[(0, 6), (1, 6), (2, 6), (3, 6), (4, 6)],
+        );
+        let line = board.line_through(2, 6, 4).unwrap();
+        assert_eq!(line.first(), Some(&(0, 6)));
+        assert_eq!(line.last(), Some(&(4, 6)));
+        assert_eq!(board.line_through(3, 3, 4), None);
+    }
+
+    #[test]
+    fn test_config_is_clamped_to_the_board() {
+        let config = BoardConfig {
+            width: 0,
+            height: 4,
+            win_length: 9,
+        };
+        assert_eq!(
+            config.clamped(),
+            BoardConfig {
+                width: 1,
+                height: 4,
+                win_length: 4,
+            }
+        );
+    }
+}
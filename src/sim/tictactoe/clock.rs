@@ -0,0 +1,79 @@
+//! Per-player game clocks
+//!
+//! Each player starts with `initial` seconds and gains `increment` seconds after
+//! every move they make. Only the clock of the player to move runs, driven by the
+//! simulation's `delta_time`, so pausing or slowing the world pauses or slows the
+//! clocks too.
+
+use enum_map::EnumMap;
+use serde::{Deserialize, Serialize};
+
+use super::Player;
+
+/// Clock settings (`clock` table of the `tictactoe` simulation params)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClockConfig {
+    /// Seconds each player starts with
+    pub initial: f32,
+    /// Seconds added to a player's clock after each of their moves
+    pub increment: f32,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            initial: 60.0,
+            increment: 2.0,
+        }
+    }
+}
+
+/// Time left for each player
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Clock {
+    config: ClockConfig,
+    remaining: EnumMap<Player, f32>,
+}
+
+impl Clock {
+    /// Creates clocks with both players on their initial time
+    pub fn new(config: ClockConfig) -> Self {
+        let config = ClockConfig {
+            initial: config.initial.max(0.0),
+            increment: config.increment.max(0.0),
+        };
+        Self {
+            config,
+            remaining: EnumMap::from_fn(|_| config.initial),
+        }
+    }
+
+    /// Returns the clock settings
+    pub fn config(&self) -> ClockConfig {
+        self.config
+    }
+
+    /// Returns the seconds `player` has left
+    pub fn remaining(&self, player: Player) -> f32 {
+        self.remaining[player]
+    }
+
+    /// Runs `player`'s clock down by `delta_time` seconds
+    /// Returns true if their time ran out
+    pub fn run(&mut self, player: Player, delta_time: f32) -> bool {
+        let remaining = &mut self.remaining[player];
+        *remaining = (*remaining - delta_time).max(0.0);
+        *remaining <= 0.0
+    }
+
+    /// Adds the increment to `player`'s clock after they move
+    pub fn add_increment(&mut self, player: Player) {
+        self.remaining[player] += self.config.increment;
+    }
+
+    /// Puts both players back on their initial time
+    pub fn reset(&mut self) {
+        self.remaining = EnumMap::from_fn(|_| self.config.initial);
+    }
+}
@@ -0,0 +1,834 @@
+pub mod ai;
+pub mod analysis;
+mod board;
+mod clock;
+pub mod notation;
+pub mod rules;
+
+use std::hash::{Hash, Hasher};
+
+use enum_map::{Enum, EnumMap};
+use serde::{Deserialize, Serialize};
+
+use super::{EventBus, Simulation, SimulationStats, StateHasher};
+
+pub use analysis::{CellAnalysis, Verdict};
+pub use board::{Board, BoardConfig};
+pub use clock::{Clock, ClockConfig};
+pub use rules::{Outcome, Rules, Variant};
+
+/// Default seconds a finished game stays on the board before it resets
+pub const DEFAULT_RESET_DELAY: f32 = 3.0;
+
+fn default_reset_delay() -> f32 {
+    DEFAULT_RESET_DELAY
+}
+
+/// TicTacToe simulation - a pure game logic implementation
+///
+/// Plays any m,n,k game: a `width` x `height` board won by `win_length` in a row,
+/// from classic 3x3 tic-tac-toe to 15x15 five-in-a-row. What a line means, and
+/// which pieces each player may place, is up to the [`Variant`]'s [`Rules`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicTacToeSimulation {
+    /// Board state
+    board: Board,
+    /// Pieces in a row needed to win
+    win_length: usize,
+    /// Rule set in play
+    #[serde(default)]
+    variant: Variant,
+    /// Per-player clocks, if the game is timed
+    #[serde(default)]
+    clock: Option<Clock>,
+    /// Line that decided the game, once it is over
+    #[serde(default)]
+    winning_line: Option<Vec<(usize, usize)>>,
+    /// Seconds a finished game is held before resetting; 0 holds it until reset
+    #[serde(default = "default_reset_delay")]
+    reset_delay: f32,
+    /// Seconds left before a finished game resets
+    #[serde(default)]
+    reset_timer: Option<f32>,
+    /// Current player
+    current_player: Player,
+    /// Game state
+    game_state: GameState,
+    /// Score tracking across multiple games
+    score: Score,
+    /// Events waiting to be published on the next world tick
+    #[serde(default)]
+    pending_events: Vec<TicTacToeEvent>,
+    /// Moves played this game, oldest first
+    #[serde(default)]
+    history: Vec<MoveRecord>,
+    /// Undone moves that can be redone, most recently undone last
+    #[serde(default)]
+    redo_stack: Vec<MoveRecord>,
+    /// Ticks this simulation has run, used to stamp moves
+    #[serde(default)]
+    ticks: u64,
+}
+
+/// A move in the game history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MoveRecord {
+    pub player: Player,
+    pub row: usize,
+    pub col: usize,
+    /// Piece placed, which only differs from the player's own in some variants
+    pub tile: Tile,
+    /// Simulation tick the move was made on (matches the world tick unless the
+    /// simulation has been paused)
+    pub tick: u64,
+}
+
+/// Events published to the world's [`EventBus`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TicTacToeEvent {
+    /// A piece was placed
+    MovePlaced {
+        row: usize,
+        col: usize,
+        player: Player,
+    },
+    /// A player completed a line, given as (row, col) cells from one end to the other
+    GameWon {
+        winner: Player,
+        line: Vec<(usize, usize)>,
+        /// Board the line was made on, for mapping cells to world space
+        config: BoardConfig,
+    },
+    /// The board filled up without a winner
+    GameDrawn,
+    /// A player's clock ran out
+    LostOnTime { loser: Player },
+}
+
+/// `tictactoe` simulation params: the board and the rule variant
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TicTacToeParams {
+    #[serde(flatten)]
+    pub board: BoardConfig,
+    pub variant: Variant,
+    /// Per-player clocks; untimed if absent
+    pub clock: Option<ClockConfig>,
+    /// Seconds a finished game stays on the board before it resets; 0 waits for
+    /// a click
+    pub reset_delay: f32,
+}
+
+impl Default for TicTacToeParams {
+    fn default() -> Self {
+        Self {
+            board: BoardConfig::default(),
+            variant: Variant::default(),
+            clock: None,
+            reset_delay: DEFAULT_RESET_DELAY,
+        }
+    }
+}
+
+/// Player markers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Enum)]
+pub enum Player {
+    X,
+    O,
+}
+
+/// Tile state on the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum Tile {
+    #[default]
+    Empty,
+    X,
+    O,
+}
+
+/// Current game state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GameState {
+    Playing,
+    Won(Player),
+    /// The other player's clock ran out
+    WonOnTime(Player),
+    Draw,
+}
+
+/// Score tracking
+#[derive(Debug, Clone, Hash, Serialize, Deserialize, Default)]
+pub struct Score {
+    wins: EnumMap<Player, u32>,
+    draws: u32,
+}
+
+impl Score {
+    /// Returns wins for a specific player
+    pub fn wins(&self, player: Player) -> u32 {
+        self.wins[player]
+    }
+
+    /// Returns total draws
+    pub fn draws(&self) -> u32 {
+        self.draws
+    }
+
+    /// Counts a finished game
+    pub(crate) fn record(&mut self, state: GameState) {
+        match state {
+            GameState::Won(player) | GameState::WonOnTime(player) => self.wins[player] += 1,
+            GameState::Draw => self.draws += 1,
+            GameState::Playing => {}
+        }
+    }
+}
+
+impl Player {
+    /// Returns the opposite player
+    pub fn opponent(self) -> Self {
+        match self {
+            Player::X => Player::O,
+            Player::O => Player::X,
+        }
+    }
+
+    /// Convert player to tile
+    pub fn to_tile(self) -> Tile {
+        match self {
+            Player::X => Tile::X,
+            Player::O => Tile::O,
+        }
+    }
+}
+
+impl TicTacToeSimulation {
+    /// Creates a new 3x3 TicTacToe game
+    pub fn new() -> Self {
+        Self::with_config(BoardConfig::default())
+    }
+
+    /// Creates a game on a board with the given dimensions and win length
+    pub fn with_config(config: BoardConfig) -> Self {
+        Self::with_variant(config, Variant::Standard)
+    }
+
+    /// Creates a game on the given board played by a variant's rules
+    pub fn with_variant(config: BoardConfig, variant: Variant) -> Self {
+        let config = config.clamped();
+        Self {
+            board: Board::new(config.width, config.height),
+            win_length: config.win_length,
+            variant,
+            clock: None,
+            winning_line: None,
+            reset_delay: DEFAULT_RESET_DELAY,
+            reset_timer: None,
+            current_player: Player::X,
+            game_state: GameState::Playing,
+            score: Score::default(),
+            pending_events: Vec::new(),
+            history: Vec::new(),
+            redo_stack: Vec::new(),
+            ticks: 0,
+        }
+    }
+
+    /// Times the game with per-player clocks
+    pub fn with_clock(mut self, config: ClockConfig) -> Self {
+        self.clock = Some(Clock::new(config));
+        self
+    }
+
+    /// Holds a finished game for `seconds` before resetting; 0 holds it until
+    /// [`reset`](Self::reset) is called
+    pub fn with_reset_delay(mut self, seconds: f32) -> Self {
+        self.reset_delay = seconds.max(0.0);
+        self
+    }
+
+    /// Attempts to place the current player's usual piece at the given board position
+    /// Returns true if the move was successful
+    ///
+    /// A new move discards any undone moves waiting to be redone.
+    pub fn make_move(&mut self, row: usize, col: usize) -> bool {
+        let tile = self.rules().pieces(self.current_player)[0];
+        self.place_piece(row, col, tile)
+    }
+
+    /// Attempts to place `tile` at the given board position, for variants that let
+    /// a player choose their piece
+    /// Returns true if the move was successful
+    pub fn place_piece(&mut self, row: usize, col: usize, tile: Tile) -> bool {
+        // Validate move
+        if !matches!(self.game_state, GameState::Playing) {
+            return false;
+        }
+        if !self
+            .rules()
+            .is_legal(&self.board, row, col, self.current_player, tile)
+        {
+            return false;
+        }
+
+        self.redo_stack.clear();
+        if let Some(clock) = &mut self.clock {
+            clock.add_increment(self.current_player);
+        }
+        self.place(MoveRecord {
+            player: self.current_player,
+            row,
+            col,
+            tile,
+            tick: self.ticks,
+        });
+        true
+    }
+
+    /// Takes back the last move, reopening a won or drawn game and removing it from
+    /// the score
+    /// Returns true if there was a move to undo
+    ///
+    /// Clocks are left alone, and a game lost on time can't be taken back.
+    pub fn undo(&mut self) -> bool {
+        if !self.can_undo() {
+            return false;
+        }
+        let Some(record) = self.history.pop() else {
+            return false;
+        };
+
+        match self.game_state {
+            GameState::Won(player) => self.score.wins[player] -= 1,
+            GameState::Draw => self.score.draws -= 1,
+            GameState::Playing | GameState::WonOnTime(_) => {}
+        }
+        // Drop the move's events if they haven't been published yet, so a paused
+        // world never hears about a move that is no longer on the board
+        if let Some(index) = self.pending_events.iter().rposition(|event| {
+            matches!(event, TicTacToeEvent::MovePlaced { row, col, .. }
+                if (*row, *col) == (record.row, record.col))
+        }) {
+            self.pending_events.truncate(index);
+        }
+        self.board.set(record.row, record.col, Tile::Empty);
+        self.current_player = record.player;
+        self.game_state = GameState::Playing;
+        self.winning_line = None;
+        self.reset_timer = None;
+        self.redo_stack.push(record);
+        true
+    }
+
+    /// Replays the most recently undone move
+    /// Returns true if there was a move to redo
+    pub fn redo(&mut self) -> bool {
+        if !self.can_redo() {
+            return false;
+        }
+        let Some(record) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.place(record);
+        true
+    }
+
+    /// Places a validated move and updates the game state and score
+    fn place(&mut self, record: MoveRecord) {
+        let MoveRecord {
+            player,
+            row,
+            col,
+            tile,
+            ..
+        } = record;
+
+        // Place the piece
+        self.board.set(row, col, tile);
+        self.history.push(record);
+        self.pending_events
+            .push(TicTacToeEvent::MovePlaced { row, col, player });
+
+        // Check for win or draw
+        let outcome = self
+            .rules()
+            .outcome(&self.board, self.win_length, row, col, player);
+        self.game_state = outcome.game_state();
+        self.score.record(self.game_state);
+        match outcome {
+            Outcome::Won { winner, line } => {
+                self.winning_line = Some(line.clone());
+                self.pending_events.push(TicTacToeEvent::GameWon {
+                    winner,
+                    line,
+                    config: self.config(),
+                });
+                self.start_reset_timer();
+            }
+            Outcome::Draw => {
+                self.pending_events.push(TicTacToeEvent::GameDrawn);
+                self.start_reset_timer();
+            }
+            Outcome::Continue => self.current_player = player.opponent(),
+        }
+    }
+
+    /// Starts counting down to the reset of a game that just ended
+    fn start_reset_timer(&mut self) {
+        self.reset_timer = (self.reset_delay > 0.0).then_some(self.reset_delay);
+    }
+
+    /// Resets the board for a new game, keeping scores
+    pub fn reset(&mut self) {
+        self.board.clear();
+        self.history.clear();
+        self.redo_stack.clear();
+        self.pending_events.clear();
+        self.current_player = Player::X;
+        self.game_state = GameState::Playing;
+        self.winning_line = None;
+        self.reset_timer = None;
+        if let Some(clock) = &mut self.clock {
+            clock.reset();
+        }
+    }
+
+    // Public accessors for rendering
+
+    /// Returns the current board state
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Returns the pieces in a row needed to win
+    pub fn win_length(&self) -> usize {
+        self.win_length
+    }
+
+    /// Returns the rule variant in play
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Returns the rules in play
+    pub fn rules(&self) -> &'static dyn Rules {
+        self.variant.rules()
+    }
+
+    /// Returns the board dimensions and win length
+    pub fn config(&self) -> BoardConfig {
+        BoardConfig {
+            width: self.board.width(),
+            height: self.board.height(),
+            win_length: self.win_length,
+        }
+    }
+
+    /// Returns the moves played this game, oldest first
+    pub fn history(&self) -> &[MoveRecord] {
+        &self.history
+    }
+
+    /// Returns true if there is a move to undo
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty() && !matches!(self.game_state, GameState::WonOnTime(_))
+    }
+
+    /// Returns true if there is an undone move to redo
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty() && !matches!(self.game_state, GameState::WonOnTime(_))
+    }
+
+    /// Returns the cells of the line that decided the game, from one end to the
+    /// other, or None while playing or after a draw or loss on time
+    ///
+    /// In variants where completing a line loses, this is the loser's line.
+    pub fn winning_line(&self) -> Option<&[(usize, usize)]> {
+        self.winning_line.as_deref()
+    }
+
+    /// Returns the seconds left before a finished game resets, or None if it isn't
+    /// counting down
+    pub fn reset_timer(&self) -> Option<f32> {
+        self.reset_timer
+    }
+
+    /// Returns the clocks, if the game is timed
+    pub fn clock(&self) -> Option<&Clock> {
+        self.clock.as_ref()
+    }
+
+    /// Returns the current player
+    pub fn current_player(&self) -> Player {
+        self.current_player
+    }
+
+    /// Returns the game state
+    pub fn game_state(&self) -> GameState {
+        self.game_state
+    }
+
+    /// Returns the world-space center of a board cell
+    ///
+    /// See [`BoardConfig::cell_center`].
+    pub fn cell_center(&self, row: usize, col: usize) -> [f32; 2] {
+        self.config().cell_center(row, col)
+    }
+
+    /// Returns the current scores
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+
+    /// Returns wins for a specific player
+    pub fn wins(&self, player: Player) -> u32 {
+        self.score.wins[player]
+    }
+
+    /// Returns total draws
+    pub fn draws(&self) -> u32 {
+        self.score.draws
+    }
+}
+
+impl Default for TicTacToeSimulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Simulation for TicTacToeSimulation {
+    fn tick(&mut self, delta_time: f32) {
+        // Ticks stamp moves; otherwise only the clock of the player to move runs,
+        // or a finished game counts down to its reset
+        self.ticks += 1;
+
+        if let Some(timer) = &mut self.reset_timer {
+            *timer -= delta_time;
+            if *timer <= 0.0 {
+                self.reset();
+            }
+            return;
+        }
+        if self.game_state != GameState::Playing {
+            return;
+        }
+        let player = self.current_player;
+        if let Some(clock) = &mut self.clock
+            && clock.run(player, delta_time)
+        {
+            self.game_state = GameState::WonOnTime(player.opponent());
+            self.score.record(self.game_state);
+            self.pending_events
+                .push(TicTacToeEvent::LostOnTime { loser: player });
+            self.start_reset_timer();
+        }
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn name(&self) -> &str {
+        "tictactoe"
+    }
+
+    fn stats(&self) -> SimulationStats {
+        let state = match self.game_state {
+            GameState::Playing => format!("{:?} to move", self.current_player),
+            GameState::Won(player) => format!("{player:?} won"),
+            GameState::WonOnTime(player) => format!("{player:?} won on time"),
+            GameState::Draw => "draw".to_string(),
+        };
+        let mut stats = SimulationStats::new();
+        stats.insert("x_wins".into(), self.score.wins[Player::X].into());
+        stats.insert("o_wins".into(), self.score.wins[Player::O].into());
+        stats.insert("draws".into(), self.score.draws.into());
+        stats.insert("variant".into(), self.variant.to_string().into());
+        if let Some(clock) = &self.clock {
+            stats.insert("x_time".into(), clock.remaining(Player::X).into());
+            stats.insert("o_time".into(), clock.remaining(Player::O).into());
+        }
+        stats.insert("state".into(), state.into());
+        stats
+    }
+
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+        self.board.hash(&mut hasher);
+        self.win_length.hash(&mut hasher);
+        self.variant.hash(&mut hasher);
+        if let Some(clock) = &self.clock {
+            hasher.write_f32(clock.config().initial);
+            hasher.write_f32(clock.config().increment);
+            hasher.write_f32(clock.remaining(Player::X));
+            hasher.write_f32(clock.remaining(Player::O));
+        }
+        self.winning_line.hash(&mut hasher);
+        hasher.write_f32(self.reset_delay);
+        self.reset_timer.is_some().hash(&mut hasher);
+        if let Some(timer) = self.reset_timer {
+            hasher.write_f32(timer);
+        }
+        self.current_player.hash(&mut hasher);
+        self.game_state.hash(&mut hasher);
+        self.score.hash(&mut hasher);
+        self.pending_events.hash(&mut hasher);
+        self.history.hash(&mut hasher);
+        self.redo_stack.hash(&mut hasher);
+        self.ticks.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn publish_events(&mut self, events: &mut EventBus) {
+        for event in self.pending_events.drain(..) {
+            events.publish(event);
+        }
+    }
+
+    fn save_state(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), serde_json::Error> {
+        *self = serde_json::from_value(state)?;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(game: &mut TicTacToeSimulation, moves: &[(usize, usize)]) {
+        for &(row, col) in moves {
+            assert!(game.make_move(row, col));
+        }
+    }
+
+    #[test]
+    fn test_history_records_player_cell_and_tick() {
+        let mut game = TicTacToeSimulation::new();
+        game.make_move(1, 1);
+        Simulation::tick(&mut game, 1.0 / 60.0);
+        Simulation::tick(&mut game, 1.0 / 60.0);
+        game.make_move(0, 2);
+
+        assert_eq!(
+            game.history(),
+            [
+                MoveRecord {
+                    player: Player::X,
+                    row: 1,
+                    col: 1,
+                    tile: Tile::X,
+                    tick: 0,
+                },
+                MoveRecord {
+                    player: Player::O,
+                    row: 0,
+                    col: 2,
+                    tile: Tile::O,
+                    tick: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_undo_reopens_won_game_and_redo_replays_it() {
+        let mut game = TicTacToeSimulation::new();
+        play(&mut game, &[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]);
+        assert_eq!(game.game_state(), GameState::Won(Player::X));
+        assert_eq!(game.wins(Player::X), 1);
+
+        assert!(game.undo());
+        assert_eq!(game.game_state(), GameState::Playing);
+        assert_eq!(game.current_player(), Player::X);
+        assert_eq!(game.wins(Player::X), 0);
+        assert_eq!(game.board().get(0, 2), Some(Tile::Empty));
+        assert_eq!(game.history().len(), 4);
+
+        assert!(game.redo());
+        assert_eq!(game.game_state(), GameState::Won(Player::X));
+        assert_eq!(game.wins(Player::X), 1);
+        assert!(!game.can_redo());
+    }
+
+    #[test]
+    fn test_undone_win_publishes_nothing() {
+        let mut game = TicTacToeSimulation::new();
+        let mut events = EventBus::new();
+        play(&mut game, &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        game.publish_events(&mut events);
+        events.clear();
+
+        // Won and taken back before the world got to publish it
+        play(&mut game, &[(0, 2)]);
+        assert!(game.undo());
+        game.publish_events(&mut events);
+        assert!(events.is_empty());
+
+        // Moves that were never published disappear the same way
+        play(&mut game, &[(2, 2), (2, 0)]);
+        assert!(game.undo());
+        game.publish_events(&mut events);
+        assert_eq!(
+            events.read::<TicTacToeEvent>(),
+            [TicTacToeEvent::MovePlaced {
+                row: 2,
+                col: 2,
+                player: Player::X
+            }]
+        );
+
+        // A reset drops whatever is still queued
+        events.clear();
+        play(&mut game, &[(2, 1)]);
+        game.reset();
+        game.publish_events(&mut events);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_variants_decide_pieces_and_winner() {
+        let config = BoardConfig::default();
+
+        // Misère: completing a line hands the game to the opponent
+        let mut misere = TicTacToeSimulation::with_variant(config, Variant::Misere);
+        play(
+            &mut misere,
+            &[(0, 0), (1, 1), (2, 1), (2, 0), (1, 0), (0, 2)],
+        );
+        assert_eq!(misere.game_state(), GameState::Won(Player::X));
+        assert_eq!(misere.wins(Player::X), 1);
+        assert!(matches!(
+            misere.pending_events.last(),
+            Some(TicTacToeEvent::GameWon { winner: Player::X, line, .. })
+                if *line == [(0, 2), (1, 1), (2, 0)]
+        ));
+
+        // Notakto: both players place X, and whoever completes a line loses
+        let mut notakto = TicTacToeSimulation::with_variant(config, Variant::Notakto);
+        play(&mut notakto, &[(0, 0), (1, 1)]);
+        assert_eq!(notakto.board().get(1, 1), Some(Tile::X));
+        assert!(!notakto.place_piece(2, 2, Tile::O));
+        play(&mut notakto, &[(2, 2)]);
+        assert_eq!(notakto.game_state(), GameState::Won(Player::O));
+
+        // Wild: either piece may be placed, and any line wins for its maker
+        let mut wild = TicTacToeSimulation::with_variant(config, Variant::Wild);
+        assert!(wild.place_piece(0, 0, Tile::O));
+        assert!(wild.place_piece(1, 1, Tile::X));
+        assert!(wild.place_piece(0, 1, Tile::O));
+        assert!(wild.place_piece(0, 2, Tile::O));
+        assert_eq!(wild.game_state(), GameState::Won(Player::O));
+        assert_eq!(wild.history()[0].tile, Tile::O);
+
+        // The standard game only takes each player's own piece
+        let mut standard = TicTacToeSimulation::new();
+        assert!(!standard.place_piece(0, 0, Tile::O));
+    }
+
+    #[test]
+    fn test_finished_game_holds_its_line_until_reset() {
+        let mut game = TicTacToeSimulation::new().with_reset_delay(1.0);
+        play(&mut game, &[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]);
+        assert_eq!(game.winning_line(), Some(&[(0, 0), (0, 1), (0, 2)][..]));
+
+        Simulation::tick(&mut game, 0.5);
+        assert_eq!(game.game_state(), GameState::Won(Player::X));
+        assert_eq!(game.reset_timer(), Some(0.5));
+        Simulation::tick(&mut game, 0.5);
+        assert_eq!(game.game_state(), GameState::Playing);
+        assert_eq!(game.winning_line(), None);
+        assert!(game.board().is_empty());
+        assert_eq!(game.wins(Player::X), 1);
+
+        // Without a delay the finished game waits for an explicit reset
+        let mut held = TicTacToeSimulation::new().with_reset_delay(0.0);
+        play(&mut held, &[(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)]);
+        Simulation::tick(&mut held, 60.0);
+        assert_eq!(held.game_state(), GameState::Won(Player::X));
+
+        // Taking back the winning move clears the line
+        assert!(held.undo());
+        assert_eq!(held.winning_line(), None);
+        assert_eq!(held.reset_timer(), None);
+    }
+
+    #[test]
+    fn test_clock_runs_for_side_to_move_until_flag_falls() {
+        let mut game = TicTacToeSimulation::new().with_clock(ClockConfig {
+            initial: 1.0,
+            increment: 0.5,
+        });
+        Simulation::tick(&mut game, 0.5);
+        assert_eq!(game.clock().unwrap().remaining(Player::X), 0.5);
+
+        // Moving adds the increment and hands the running clock to O
+        play(&mut game, &[(1, 1)]);
+        Simulation::tick(&mut game, 0.5);
+        let clock = game.clock().unwrap();
+        assert_eq!(clock.remaining(Player::X), 1.0);
+        assert_eq!(clock.remaining(Player::O), 0.5);
+
+        Simulation::tick(&mut game, 0.5);
+        assert_eq!(game.game_state(), GameState::WonOnTime(Player::X));
+        assert_eq!(game.wins(Player::X), 1);
+        assert_eq!(
+            game.pending_events.last(),
+            Some(&TicTacToeEvent::LostOnTime { loser: Player::O })
+        );
+        assert!(!game.make_move(0, 0));
+        assert!(!game.undo());
+
+        game.reset();
+        assert_eq!(game.game_state(), GameState::Playing);
+        assert_eq!(game.clock().unwrap().remaining(Player::O), 1.0);
+    }
+
+    #[test]
+    fn test_undo_draw_and_new_move_discards_redo() {
+        let mut game = TicTacToeSimulation::new();
+        // X O X / X O O / O X X
+        play(
+            &mut game,
+            &[
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 1),
+                (1, 0),
+                (1, 2),
+                (2, 1),
+                (2, 0),
+                (2, 2),
+            ],
+        );
+        assert_eq!(game.game_state(), GameState::Draw);
+
+        assert!(game.undo());
+        assert!(game.undo());
+        assert_eq!(game.draws(), 0);
+        assert_eq!(game.current_player(), Player::O);
+        assert!(game.can_redo());
+
+        // Playing a different move starts a new line of play
+        assert!(game.make_move(2, 2));
+        assert!(!game.can_redo());
+        assert!(!game.redo());
+
+        game.reset();
+        assert!(!game.can_undo());
+        assert!(!game.undo());
+    }
+}
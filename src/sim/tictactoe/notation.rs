@@ -0,0 +1,461 @@
+//! Text notation for tic-tac-toe positions and games
+//!
+//! A position lists the board rows top to bottom, separated by `/`, using `x`, `o`
+//! and `.` for empty cells, followed by the side to move and, if it isn't 3, the
+//! win length:
+//!
+//! ```text
+//! x.o/.x./..o x
+//! ...../..x../..o../...../..... x 4
+//! ```
+//!
+//! When the game is over the side given is the player who made the last move.
+//!
+//! A game is a space-separated move list such as `X:b2 O:a1 X:c3`. Cells are named
+//! by column letter and row number, with `a1` the top-left cell; boards wider than
+//! 26 columns continue with `aa`, `ab` and so on.
+//!
+//! Notation describes standard games: parsed games play by
+//! [`Variant::Standard`](super::Variant::Standard) and positions are checked
+//! against its rules.
+
+use std::fmt::Write as _;
+
+use super::{Board, BoardConfig, GameState, Player, TicTacToeSimulation, Tile};
+
+/// Errors produced while parsing position or game notation
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NotationError {
+    #[error("position is empty")]
+    Empty,
+    #[error("expected the side to move after the board, e.g. `x.o/.x./..o x`")]
+    MissingSide,
+    #[error("invalid side to move `{0}`, expected `x` or `o`")]
+    InvalidSide(String),
+    #[error("invalid win length `{0}`, expected a positive number")]
+    InvalidWinLength(String),
+    #[error("unexpected `{0}` after the end of the position")]
+    TrailingInput(String),
+    #[error("invalid tile `{found}` at row {row}, column {col}; expected `x`, `o` or `.`")]
+    InvalidTile { row: usize, col: usize, found: char },
+    #[error("row {row} is empty")]
+    EmptyRow { row: usize },
+    #[error("row {row} has {found} cells but row 1 has {expected}")]
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("win length {win_length} does not fit on a {width}x{height} board")]
+    WinLengthTooLong {
+        win_length: usize,
+        width: usize,
+        height: usize,
+    },
+    #[error(
+        "x has {x} pieces and o has {o}; x moves first, so it must have as many as o or one more"
+    )]
+    PieceCount { x: usize, o: usize },
+    #[error("both players have a winning line")]
+    BothWon,
+    #[error("{winner:?} has a winning line but {last:?} moved last")]
+    WinnerMovedFirst { winner: Player, last: Player },
+    #[error("side to move is {found:?} but the pieces on the board give {expected:?}")]
+    WrongSide { expected: Player, found: Player },
+    #[error("move {index}: expected `X:<cell>` or `O:<cell>`, found `{token}`")]
+    InvalidMove { index: usize, token: String },
+    #[error(
+        "move {index}: invalid cell `{cell}`, expected a column letter and row number like `b2`"
+    )]
+    InvalidCell { index: usize, cell: String },
+    #[error("move {index}: {cell} is off the {width}x{height} board")]
+    OffBoard {
+        index: usize,
+        cell: String,
+        width: usize,
+        height: usize,
+    },
+    #[error("move {index}: {cell} is already taken")]
+    Occupied { index: usize, cell: String },
+    #[error("move {index}: {found:?} moved but it was {expected:?}'s turn")]
+    OutOfTurn {
+        index: usize,
+        expected: Player,
+        found: Player,
+    },
+    #[error("move {index}: the game was already over")]
+    GameOver { index: usize },
+}
+
+/// Returns the notation name of a cell, e.g. `b2` for (1, 1)
+pub fn cell_name(row: usize, col: usize) -> String {
+    let mut letters = Vec::new();
+    let mut n = col + 1;
+    while n > 0 {
+        n -= 1;
+        letters.push(b'a' + (n % 26) as u8);
+        n /= 26;
+    }
+    letters.reverse();
+    format!("{}{}", String::from_utf8_lossy(&letters), row + 1)
+}
+
+/// Parses a cell name such as `b2` into (row, col)
+pub fn parse_cell(name: &str) -> Option<(usize, usize)> {
+    let split = name.find(|c: char| !c.is_ascii_lowercase())?;
+    let (letters, digits) = name.split_at(split);
+    if letters.is_empty() || digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let mut col = 0usize;
+    for byte in letters.bytes() {
+        col = col
+            .checked_mul(26)?
+            .checked_add((byte - b'a' + 1) as usize)?;
+    }
+    let row: usize = digits.parse().ok()?;
+    Some((row.checked_sub(1)?, col - 1))
+}
+
+impl TicTacToeSimulation {
+    /// Formats the board, side to move and win length as position notation
+    pub fn to_position(&self) -> String {
+        let mut text = String::new();
+        for (index, row) in self.board.rows().enumerate() {
+            if index > 0 {
+                text.push('/');
+            }
+            text.extend(row.iter().map(|tile| match tile {
+                Tile::Empty => '.',
+                Tile::X => 'x',
+                Tile::O => 'o',
+            }));
+        }
+        text.push(' ');
+        text.push(player_char(self.current_player));
+        if self.win_length != BoardConfig::default().win_length {
+            write!(text, " {}", self.win_length).expect("writing to a String cannot fail");
+        }
+        text
+    }
+
+    /// Creates a game at the position described by `notation`
+    ///
+    /// The game state is derived from the board. The new game has no move history
+    /// and a zero score.
+    pub fn from_position(notation: &str) -> Result<Self, NotationError> {
+        let mut parts = notation.split_whitespace();
+        let rows = parts.next().ok_or(NotationError::Empty)?;
+        let side = match parts.next().ok_or(NotationError::MissingSide)? {
+            "x" => Player::X,
+            "o" => Player::O,
+            other => return Err(NotationError::InvalidSide(other.to_string())),
+        };
+        let win_length = match parts.next() {
+            Some(text) => text
+                .parse::<usize>()
+                .ok()
+                .filter(|&length| length > 0)
+                .ok_or_else(|| NotationError::InvalidWinLength(text.to_string()))?,
+            None => BoardConfig::default().win_length,
+        };
+        if let Some(extra) = parts.next() {
+            return Err(NotationError::TrailingInput(extra.to_string()));
+        }
+
+        let rows: Vec<&str> = rows.split('/').collect();
+        let width = rows[0].chars().count();
+        let height = rows.len();
+        if width == 0 {
+            return Err(NotationError::EmptyRow { row: 1 });
+        }
+        if win_length > width.max(height) {
+            return Err(NotationError::WinLengthTooLong {
+                win_length,
+                width,
+                height,
+            });
+        }
+
+        let mut game = Self::with_config(BoardConfig {
+            width,
+            height,
+            win_length,
+        });
+        for (row, text) in rows.iter().enumerate() {
+            let found = text.chars().count();
+            if found != width {
+                return Err(NotationError::RaggedRow {
+                    row: row + 1,
+                    expected: width,
+                    found,
+                });
+            }
+            for (col, ch) in text.chars().enumerate() {
+                let tile = match ch {
+                    '.' => Tile::Empty,
+                    'x' => Tile::X,
+                    'o' => Tile::O,
+                    found => {
+                        return Err(NotationError::InvalidTile {
+                            row: row + 1,
+                            col: col + 1,
+                            found,
+                        });
+                    }
+                };
+                game.board.set(row, col, tile);
+            }
+        }
+
+        let (state, expected) = derive_state(&game.board, win_length)?;
+        if side != expected {
+            return Err(NotationError::WrongSide {
+                expected,
+                found: side,
+            });
+        }
+        game.game_state = state;
+        game.current_player = side;
+        Ok(game)
+    }
+
+    /// Formats the moves played this game as a move list, e.g. `X:b2 O:a1`
+    pub fn to_moves(&self) -> String {
+        self.history
+            .iter()
+            .map(|record| format!("{:?}:{}", record.player, cell_name(record.row, record.col)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Creates a game on a `config` board by playing the moves in `notation`
+    ///
+    /// Every move is validated, so the resulting history matches the notation
+    /// exactly.
+    pub fn from_moves(config: BoardConfig, notation: &str) -> Result<Self, NotationError> {
+        let mut game = Self::with_config(config);
+        for (index, token) in notation.split_whitespace().enumerate() {
+            let index = index + 1;
+            let invalid = || NotationError::InvalidMove {
+                index,
+                token: token.to_string(),
+            };
+            let (player, cell) = token.split_once(':').ok_or_else(invalid)?;
+            let player = match player {
+                "X" | "x" => Player::X,
+                "O" | "o" => Player::O,
+                _ => return Err(invalid()),
+            };
+            let (row, col) = parse_cell(cell).ok_or_else(|| NotationError::InvalidCell {
+                index,
+                cell: cell.to_string(),
+            })?;
+
+            if game.game_state != GameState::Playing {
+                return Err(NotationError::GameOver { index });
+            }
+            if player != game.current_player {
+                return Err(NotationError::OutOfTurn {
+                    index,
+                    expected: game.current_player,
+                    found: player,
+                });
+            }
+            match game.board.get(row, col) {
+                None => {
+                    return Err(NotationError::OffBoard {
+                        index,
+                        cell: cell.to_string(),
+                        width: game.board.width(),
+                        height: game.board.height(),
+                    });
+                }
+                Some(Tile::Empty) => {}
+                Some(_) => {
+                    return Err(NotationError::Occupied {
+                        index,
+                        cell: cell.to_string(),
+                    });
+                }
+            }
+            game.make_move(row, col);
+        }
+        // Moves from notation are setup, not play
+        game.score = Default::default();
+        game.pending_events.clear();
+        Ok(game)
+    }
+}
+
+/// Returns the notation character for a player's pieces
+fn player_char(player: Player) -> char {
+    match player {
+        Player::X => 'x',
+        Player::O => 'o',
+    }
+}
+
+/// Works out the game state and the side to report from the pieces on the board
+fn derive_state(board: &Board, win_length: usize) -> Result<(GameState, Player), NotationError> {
+    let count = |tile| {
+        board
+            .rows()
+            .flatten()
+            .filter(|&&other| other == tile)
+            .count()
+    };
+    let (x, o) = (count(Tile::X), count(Tile::O));
+    if x != o && x != o + 1 {
+        return Err(NotationError::PieceCount { x, o });
+    }
+    let last = if x > o { Player::X } else { Player::O };
+    let next = last.opponent();
+
+    let has_line = |player: Player| {
+        let tile = player.to_tile();
+        (0..board.height()).any(|row| {
+            (0..board.width()).any(|col| {
+                board.get(row, col) == Some(tile)
+                    && board.line_through(row, col, win_length).is_some()
+            })
+        })
+    };
+    let winner = match (has_line(Player::X), has_line(Player::O)) {
+        (true, true) => return Err(NotationError::BothWon),
+        (true, false) => Some(Player::X),
+        (false, true) => Some(Player::O),
+        (false, false) => None,
+    };
+    match winner {
+        Some(winner) if winner != last => Err(NotationError::WinnerMovedFirst { winner, last }),
+        Some(winner) => Ok((GameState::Won(winner), winner)),
+        None if board.is_full() => Ok((GameState::Draw, last)),
+        None => Ok((GameState::Playing, next)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_round_trip() {
+        for notation in [
+            "... x",
+            "x.o/.x./..o x",
+            "xxx/oo./... x",
+            "xox/xoo/oxx x",
+            "...../..x../..o../...../..... x 4",
+        ] {
+            let game = TicTacToeSimulation::from_position(notation).unwrap();
+            assert_eq!(game.to_position(), notation);
+        }
+
+        let mut game = TicTacToeSimulation::new();
+        for (row, col) in [(1, 1), (0, 0), (2, 2)] {
+            game.make_move(row, col);
+        }
+        assert_eq!(game.to_position(), "o../.x./..x o");
+    }
+
+    #[test]
+    fn test_position_derives_game_state() {
+        let won = TicTacToeSimulation::from_position("xxx/oo./... x").unwrap();
+        assert_eq!(won.game_state(), GameState::Won(Player::X));
+
+        let drawn = TicTacToeSimulation::from_position("xox/xoo/oxx x").unwrap();
+        assert_eq!(drawn.game_state(), GameState::Draw);
+
+        let playing = TicTacToeSimulation::from_position("x.o/.x./..o x").unwrap();
+        assert_eq!(playing.game_state(), GameState::Playing);
+        assert_eq!(playing.current_player(), Player::X);
+        assert_eq!(playing.board().get(0, 2), Some(Tile::O));
+    }
+
+    #[test]
+    fn test_position_errors() {
+        let error = |text| {
+            TicTacToeSimulation::from_position(text)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(error("   "), "position is empty");
+        assert_eq!(
+            error("x.o/.x./..o"),
+            "expected the side to move after the board, e.g. `x.o/.x./..o x`"
+        );
+        assert_eq!(
+            error("x.o/.X./..o x"),
+            "invalid tile `X` at row 2, column 2; expected `x`, `o` or `.`"
+        );
+        assert_eq!(error("x.o/.x/..o x"), "row 2 has 2 cells but row 1 has 3");
+        assert_eq!(error("/... x"), "row 1 is empty");
+        assert_eq!(
+            error("xx./.../... o"),
+            "x has 2 pieces and o has 0; x moves first, so it must have as many as o or one more"
+        );
+        assert_eq!(
+            error("x.o/.x./... x"),
+            "side to move is X but the pieces on the board give O"
+        );
+        assert_eq!(error("xxx/ooo/x.. x"), "both players have a winning line");
+        assert_eq!(
+            error("ooo/xx./x.x x"),
+            "O has a winning line but X moved last"
+        );
+        assert_eq!(error("... x 4"), "win length 4 does not fit on a 3x1 board");
+        assert_eq!(
+            error("... x 3 extra"),
+            "unexpected `extra` after the end of the position"
+        );
+    }
+
+    #[test]
+    fn test_moves_round_trip() {
+        let notation = "X:b2 O:a1 X:c3 O:a3 X:a2 O:c2 X:b1 O:b3 X:c1";
+        let game = TicTacToeSimulation::from_moves(BoardConfig::default(), notation).unwrap();
+        assert_eq!(game.to_moves(), notation);
+        assert_eq!(game.game_state(), GameState::Draw);
+        assert_eq!(game.history().len(), 9);
+        assert_eq!(game.draws(), 0);
+    }
+
+    #[test]
+    fn test_move_errors() {
+        let error = |text| {
+            TicTacToeSimulation::from_moves(BoardConfig::default(), text)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error("X:b2 b1"),
+            "move 2: expected `X:<cell>` or `O:<cell>`, found `b1`"
+        );
+        assert_eq!(
+            error("X:2b"),
+            "move 1: invalid cell `2b`, expected a column letter and row number like `b2`"
+        );
+        assert_eq!(error("X:d1"), "move 1: d1 is off the 3x3 board");
+        assert_eq!(error("X:b2 O:b2"), "move 2: b2 is already taken");
+        assert_eq!(error("X:b2 X:a1"), "move 2: X moved but it was O's turn");
+        assert_eq!(
+            error("X:a1 O:b1 X:a2 O:b2 X:a3 O:b3"),
+            "move 6: the game was already over"
+        );
+    }
+
+    #[test]
+    fn test_cell_names() {
+        assert_eq!(cell_name(0, 0), "a1");
+        assert_eq!(cell_name(14, 14), "o15");
+        assert_eq!(cell_name(0, 26), "aa1");
+        for (row, col) in [(0, 0), (9, 25), (3, 26), (0, 701), (2, 702)] {
+            assert_eq!(parse_cell(&cell_name(row, col)), Some((row, col)));
+        }
+        assert_eq!(parse_cell("a0"), None);
+        assert_eq!(parse_cell("b"), None);
+        assert_eq!(parse_cell("B2"), None);
+    }
+}
@@ -0,0 +1,216 @@
+//! Rule sets for tic-tac-toe variants
+//!
+//! A [`Rules`] implementation decides which pieces a player may place, where they
+//! may go and how a move ends the game. [`Variant`] names the built-in rule sets so
+//! the choice can live in config and saved games:
+//!
+//! - **Standard**: completing a line of your own pieces wins
+//! - **Misère**: completing a line of your own pieces loses
+//! - **Wild**: either player may place X or O, and completing a line of either wins
+//! - **Notakto**: both players place X, and completing a line loses
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Board, GameState, Player, Tile};
+
+/// How a move left the game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The game goes on with the other player to move
+    Continue,
+    /// The game is decided; `line` is the completed line that ended it
+    Won {
+        winner: Player,
+        line: Vec<(usize, usize)>,
+    },
+    /// The board filled up without a decisive line
+    Draw,
+}
+
+impl Outcome {
+    /// Returns the game state this outcome leads to
+    pub fn game_state(&self) -> GameState {
+        match self {
+            Outcome::Continue => GameState::Playing,
+            Outcome::Won { winner, .. } => GameState::Won(*winner),
+            Outcome::Draw => GameState::Draw,
+        }
+    }
+}
+
+/// Rules of a tic-tac-toe variant
+pub trait Rules {
+    /// Returns the pieces `player` may place, the usual one first
+    fn pieces(&self, player: Player) -> &'static [Tile];
+
+    /// Returns true if `player` may place `tile` at (row, col)
+    fn is_legal(&self, board: &Board, row: usize, col: usize, player: Player, tile: Tile) -> bool {
+        board.get(row, col) == Some(Tile::Empty) && self.pieces(player).contains(&tile)
+    }
+
+    /// Decides the outcome after `player` placed a piece at (row, col)
+    fn outcome(
+        &self,
+        board: &Board,
+        win_length: usize,
+        row: usize,
+        col: usize,
+        player: Player,
+    ) -> Outcome;
+}
+
+/// Built-in rule sets, selectable in config
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Variant {
+    #[default]
+    Standard,
+    Misere,
+    Wild,
+    Notakto,
+}
+
+impl Variant {
+    /// Every built-in variant
+    pub const ALL: [Variant; 4] = [
+        Variant::Standard,
+        Variant::Misere,
+        Variant::Wild,
+        Variant::Notakto,
+    ];
+
+    /// Returns the rules this variant plays by
+    pub fn rules(self) -> &'static dyn Rules {
+        match self {
+            Variant::Standard => &Standard,
+            Variant::Misere => &Misere,
+            Variant::Wild => &Wild,
+            Variant::Notakto => &Notakto,
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Variant::Standard => "Standard",
+            Variant::Misere => "Misère",
+            Variant::Wild => "Wild",
+            Variant::Notakto => "Notakto",
+        })
+    }
+}
+
+/// Each player places their own piece; a line wins
+pub struct Standard;
+
+/// Each player places their own piece; a line loses
+pub struct Misere;
+
+/// Either player places either piece; any line wins for whoever made it
+pub struct Wild;
+
+/// Both players place X; a line loses
+pub struct Notakto;
+
+/// Returns the pieces a player places in the standard game
+fn own_piece(player: Player) -> &'static [Tile] {
+    match player {
+        Player::X => &[Tile::X],
+        Player::O => &[Tile::O],
+    }
+}
+
+/// Scores a move where completing a line ends the game, won by `player` if
+/// `line_wins` and by the opponent otherwise
+fn line_outcome(
+    board: &Board,
+    win_length: usize,
+    (row, col): (usize, usize),
+    player: Player,
+    line_wins: bool,
+) -> Outcome {
+    if let Some(line) = board.line_through(row, col, win_length) {
+        let winner = if line_wins { player } else { player.opponent() };
+        Outcome::Won { winner, line }
+    } else if board.is_full() {
+        Outcome::Draw
+    } else {
+        Outcome::Continue
+    }
+}
+
+impl Rules for Standard {
+    fn pieces(&self, player: Player) -> &'static [Tile] {
+        own_piece(player)
+    }
+
+    fn outcome(
+        &self,
+        board: &Board,
+        win_length: usize,
+        row: usize,
+        col: usize,
+        player: Player,
+    ) -> Outcome {
+        line_outcome(board, win_length, (row, col), player, true)
+    }
+}
+
+impl Rules for Misere {
+    fn pieces(&self, player: Player) -> &'static [Tile] {
+        own_piece(player)
+    }
+
+    fn outcome(
+        &self,
+        board: &Board,
+        win_length: usize,
+        row: usize,
+        col: usize,
+        player: Player,
+    ) -> Outcome {
+        line_outcome(board, win_length, (row, col), player, false)
+    }
+}
+
+impl Rules for Wild {
+    fn pieces(&self, player: Player) -> &'static [Tile] {
+        match player {
+            Player::X => &[Tile::X, Tile::O],
+            Player::O => &[Tile::O, Tile::X],
+        }
+    }
+
+    fn outcome(
+        &self,
+        board: &Board,
+        win_length: usize,
+        row: usize,
+        col: usize,
+        player: Player,
+    ) -> Outcome {
+        line_outcome(board, win_length, (row, col), player, true)
+    }
+}
+
+impl Rules for Notakto {
+    fn pieces(&self, _player: Player) -> &'static [Tile] {
+        &[Tile::X]
+    }
+
+    fn outcome(
+        &self,
+        board: &Board,
+        win_length: usize,
+        row: usize,
+        col: usize,
+        player: Player,
+    ) -> Outcome {
+        line_outcome(board, win_length, (row, col), player, false)
+    }
+}
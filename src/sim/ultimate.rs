@@ -0,0 +1,299 @@
+//! Ultimate tic-tac-toe simulation
+//!
+//! Nine 3x3 sub-boards laid out in a 3x3 grid. The cell a player picks inside a
+//! sub-board sends the opponent to the sub-board in the same position; if that
+//! sub-board is already won or full, the opponent may play anywhere. Winning a
+//! sub-board claims its square on the outer board, and three claimed squares in a
+//! row win the game.
+//!
+//! Cells are addressed as global (row, col) on the 9x9 grid, so sub-board
+//! `(row / 3, col / 3)` holds cell `(row % 3, col % 3)`.
+
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+
+use super::tictactoe::{Board, GameState, Player, Score, Tile};
+use super::{Simulation, SimulationStats, StateHasher};
+
+/// Cells along each side of a sub-board (and sub-boards along each side of the grid)
+pub const BLOCK: usize = 3;
+
+/// Cells along each side of the full grid
+pub const SIZE: usize = BLOCK * BLOCK;
+
+/// Ultimate tic-tac-toe game logic
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
+pub struct UltimateTicTacToeSimulation {
+    /// Sub-boards in row-major order
+    boards: Vec<Board>,
+    /// State of each sub-board, in the same order
+    board_states: Vec<GameState>,
+    /// Outer board: the winner of each sub-board, or empty
+    outer: Board,
+    /// Sub-board the current player must play in, or None to play anywhere
+    target: Option<(usize, usize)>,
+    current_player: Player,
+    game_state: GameState,
+    score: Score,
+}
+
+impl UltimateTicTacToeSimulation {
+    /// Creates a new game
+    pub fn new() -> Self {
+        Self {
+            boards: vec![Board::new(BLOCK, BLOCK); BLOCK * BLOCK],
+            board_states: vec![GameState::Playing; BLOCK * BLOCK],
+            outer: Board::new(BLOCK, BLOCK),
+            target: None,
+            current_player: Player::X,
+            game_state: GameState::Playing,
+            score: Score::default(),
+        }
+    }
+
+    /// Attempts to make a move at global cell (row, col), each 0-8
+    /// Returns true if the move was successful
+    pub fn make_move(&mut self, row: usize, col: usize) -> bool {
+        if !self.is_legal(row, col) {
+            return false;
+        }
+
+        let player = self.current_player;
+        let sub = (row / BLOCK, col / BLOCK);
+        let cell = (row % BLOCK, col % BLOCK);
+        let index = sub.0 * BLOCK + sub.1;
+
+        let board = &mut self.boards[index];
+        board.set(cell.0, cell.1, player.to_tile());
+        if board.line_through(cell.0, cell.1, BLOCK).is_some() {
+            self.board_states[index] = GameState::Won(player);
+            self.outer.set(sub.0, sub.1, player.to_tile());
+        } else if board.is_full() {
+            self.board_states[index] = GameState::Draw;
+        }
+
+        // The opponent is sent to the sub-board matching the cell just played
+        self.target = self.is_open(cell).then_some(cell);
+
+        if self.outer.line_through(sub.0, sub.1, BLOCK).is_some() {
+            self.game_state = GameState::Won(player);
+            self.score.record(self.game_state);
+        } else if self.board_states.iter().all(|&s| s != GameState::Playing) {
+            self.game_state = GameState::Draw;
+            self.score.record(self.game_state);
+        } else {
+            self.current_player = player.opponent();
+        }
+        true
+    }
+
+    /// Returns true if the current player may play at global cell (row, col)
+    pub fn is_legal(&self, row: usize, col: usize) -> bool {
+        if self.game_state != GameState::Playing || row >= SIZE || col >= SIZE {
+            return false;
+        }
+        let sub = (row / BLOCK, col / BLOCK);
+        if self.target.is_some_and(|target| target != sub) || !self.is_open(sub) {
+            return false;
+        }
+        self.tile(row, col) == Tile::Empty
+    }
+
+    /// Resets the boards for a new game, keeping scores
+    pub fn reset(&mut self) {
+        *self = Self {
+            score: self.score.clone(),
+            ..Self::new()
+        };
+    }
+
+    /// Returns true if the sub-board at (row, col) is still being played
+    fn is_open(&self, (row, col): (usize, usize)) -> bool {
+        self.board_states[row * BLOCK + col] == GameState::Playing
+    }
+
+    /// Returns the tile at global cell (row, col)
+    pub fn tile(&self, row: usize, col: usize) -> Tile {
+        self.boards[(row / BLOCK) * BLOCK + col / BLOCK]
+            .get(row % BLOCK, col % BLOCK)
+            .unwrap_or_default()
+    }
+
+    /// Returns the state of the sub-board at (row, col), each 0-2
+    pub fn board_state(&self, row: usize, col: usize) -> GameState {
+        self.board_states[row * BLOCK + col]
+    }
+
+    /// Returns the sub-board the current player must play in, or None if any open
+    /// sub-board is allowed
+    pub fn target(&self) -> Option<(usize, usize)> {
+        self.target
+    }
+
+    /// Returns the current player
+    pub fn current_player(&self) -> Player {
+        self.current_player
+    }
+
+    /// Returns the game state
+    pub fn game_state(&self) -> GameState {
+        self.game_state
+    }
+
+    /// Returns the current scores
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+}
+
+impl Default for UltimateTicTacToeSimulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Simulation for UltimateTicTacToeSimulation {
+    fn tick(&mut self, _delta_time: f32) {
+        // Turn-based, no continuous simulation needed
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn name(&self) -> &str {
+        "ultimate"
+    }
+
+    fn stats(&self) -> SimulationStats {
+        let state = match self.game_state {
+            GameState::Playing => format!("{:?} to move", self.current_player),
+            GameState::Won(player) => format!("{player:?} won"),
+            GameState::WonOnTime(player) => format!("{player:?} won on time"),
+            GameState::Draw => "draw".to_string(),
+        };
+        let claimed = |player| {
+            self.board_states
+                .iter()
+                .filter(|&&s| s == GameState::Won(player))
+                .count()
+        };
+        let mut stats = SimulationStats::new();
+        stats.insert("x_wins".into(), self.score.wins(Player::X).into());
+        stats.insert("o_wins".into(), self.score.wins(Player::O).into());
+        stats.insert("draws".into(), self.score.draws().into());
+        stats.insert("x_boards".into(), claimed(Player::X).into());
+        stats.insert("o_boards".into(), claimed(Player::O).into());
+        stats.insert("state".into(), state.into());
+        stats
+    }
+
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn save_state(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), serde_json::Error> {
+        *self = serde_json::from_value(state)?;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(game: &mut UltimateTicTacToeSimulation, moves: &[(usize, usize)]) {
+        for &(row, col) in moves {
+            assert!(game.make_move(row, col), "move ({row}, {col}) rejected");
+        }
+    }
+
+    #[test]
+    fn test_move_sends_opponent_to_matching_board() {
+        let mut game = UltimateTicTacToeSimulation::new();
+        assert_eq!(game.target(), None);
+
+        // Top-right cell of the center board sends O to the top-right board
+        play(&mut game, &[(3, 5)]);
+        assert_eq!(game.target(), Some((0, 2)));
+        assert!(!game.make_move(4, 4));
+        assert!(!game.make_move(3, 5));
+        assert!(game.make_move(1, 7));
+        assert_eq!(game.target(), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_decided_board_frees_the_next_move() {
+        let mut game = UltimateTicTacToeSimulation::new();
+        // X takes the top row of the top-middle board; its last cell points back at it
+        play(&mut game, &[(0, 3), (0, 1), (0, 5), (0, 7), (0, 4)]);
+        assert_eq!(game.board_state(0, 1), GameState::Won(Player::X));
+        assert_eq!(game.target(), None);
+
+        assert!(!game.make_move(1, 4), "won boards are closed");
+        assert!(game.make_move(8, 8));
+        assert_eq!(game.target(), Some((2, 2)));
+    }
+
+    #[test]
+    fn test_three_boards_in_a_row_win_the_game() {
+        let mut game = UltimateTicTacToeSimulation::new();
+        play(
+            &mut game,
+            &[
+                (2, 0),
+                (8, 0),
+                (8, 2),
+                (6, 6),
+                (0, 2),
+                (1, 6),
+                (3, 2),
+                (2, 6),
+                (6, 2),
+                (0, 6),
+                (1, 1),
+                (4, 5),
+                (4, 6),
+                (3, 0),
+                (6, 0),
+                (2, 5),
+                (6, 8),
+                (4, 0),
+                (5, 1),
+                (6, 4),
+                (1, 4),
+                (5, 4),
+                (6, 5),
+                (8, 3),
+                (7, 1),
+                (4, 3),
+                (5, 0),
+                (1, 3),
+                (5, 2),
+            ],
+        );
+        assert_eq!(game.game_state(), GameState::Won(Player::X));
+        assert_eq!(game.score().wins(Player::X), 1);
+        assert!(!game.make_move(8, 8));
+
+        game.reset();
+        assert_eq!(game.game_state(), GameState::Playing);
+        assert_eq!(game.target(), None);
+        assert_eq!(game.score().wins(Player::X), 1);
+    }
+}
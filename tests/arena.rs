@@ -0,0 +1,92 @@
+//! Integration tests for the strategy arena
+
+use oil_pool::arena::{self, ArenaError, ArenaOptions, Strategy};
+use oil_pool::headless::OutputFormat;
+
+fn options(strategies: &[Strategy], games: u32) -> ArenaOptions {
+    ArenaOptions {
+        strategies: strategies.to_vec(),
+        games,
+        seed: 42,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_plays_every_pair() {
+    let report = arena::run(&options(&Strategy::ALL, 4)).unwrap();
+
+    assert_eq!(report.matchups.len(), 6);
+    for matchup in &report.matchups {
+        assert_ne!(matchup.first, matchup.second);
+        let total = matchup.wins.count + matchup.draws.count + matchup.losses.count;
+        assert_eq!(total, 4);
+        assert_eq!(matchup.aborted, 0);
+    }
+    assert_eq!(report.standings.len(), 4);
+    assert!(report.standings.iter().all(|s| s.games == 12));
+}
+
+#[test]
+fn test_minimax_never_loses_to_random() {
+    let report = arena::run(&options(&[Strategy::Random, Strategy::Minimax], 40)).unwrap();
+
+    let matchup = &report.matchups[0];
+    assert_eq!(matchup.first, Strategy::Random);
+    assert_eq!(matchup.wins.count, 0);
+    assert!(matchup.losses.count > 20);
+    assert!(matchup.wins.high < 0.15);
+    assert_eq!(report.standings[0].strategy, Strategy::Minimax);
+}
+
+#[test]
+fn test_same_seed_gives_same_report() {
+    let strategies = [Strategy::Random, Strategy::Greedy];
+    let a = arena::run(&options(&strategies, 50)).unwrap();
+    let b = arena::run(&options(&strategies, 50)).unwrap();
+    assert_eq!(a, b);
+
+    let mut other = options(&strategies, 50);
+    other.seed = 43;
+    assert_ne!(arena::run(&other).unwrap().matchups, a.matchups);
+}
+
+#[test]
+fn test_alternates_who_moves_first() {
+    let report = arena::run(&options(&[Strategy::Greedy, Strategy::Random], 100)).unwrap();
+
+    let matchup = &report.matchups[0];
+    assert!(matchup.wins_as_x > 0);
+    assert!(matchup.wins_as_o > 0);
+    assert_eq!(matchup.wins_as_x + matchup.wins_as_o, matchup.wins.count);
+    assert_eq!(report.standings[0].strategy, Strategy::Greedy);
+}
+
+#[test]
+fn test_report_formats() {
+    let report = arena::run(&options(&[Strategy::Random, Strategy::Greedy], 10)).unwrap();
+
+    let text = report.render(OutputFormat::Text);
+    assert!(text.starts_with("10 games per matchup on 3x3"));
+    assert!(text.contains("greedy"));
+    assert!(text.contains("Draws"));
+    let matchup = &report.matchups[0];
+    assert!(text.contains(&format!("{} / {}", matchup.wins_as_x, matchup.wins_as_o)));
+
+    let json: serde_json::Value = serde_json::from_str(&report.render(OutputFormat::Json)).unwrap();
+    assert_eq!(json["games_per_matchup"], 10);
+    assert_eq!(json["matchups"][0]["first"], "random");
+    assert_eq!(json["variant"], "standard");
+}
+
+#[test]
+fn test_rejects_empty_arena() {
+    assert!(matches!(
+        arena::run(&options(&[Strategy::Minimax, Strategy::Minimax], 10)),
+        Err(ArenaError::TooFewStrategies)
+    ));
+    assert!(matches!(
+        arena::run(&options(&[Strategy::Random, Strategy::Greedy], 0)),
+        Err(ArenaError::NoGames)
+    ));
+}
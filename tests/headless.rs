@@ -0,0 +1,71 @@
+//! Integration tests for the headless simulation runner
+
+use oil_pool::headless::{self, HeadlessError, HeadlessOptions, OutputFormat, RunLength};
+use oil_pool::sim::SimConfig;
+
+fn options(length: RunLength) -> HeadlessOptions {
+    HeadlessOptions {
+        length,
+        seed: Some(42),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_runs_for_ticks_and_grows_leaves() {
+    let summary = headless::run(&SimConfig::default(), &options(RunLength::Ticks(300))).unwrap();
+
+    assert_eq!(summary.ticks, 300);
+    assert_eq!(summary.seed, 42);
+    let leaf = summary
+        .simulations
+        .iter()
+        .find(|sim| sim.name == "leaf")
+        .expect("leaf simulation in summary");
+    assert_eq!(leaf.stats["vines"], 4);
+    assert!(leaf.stats["leaves"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn test_runs_for_sim_seconds() {
+    let mut opts = options(RunLength::Seconds(2.0));
+    opts.dt = Some(0.05);
+    opts.time_scale = Some(2.0);
+
+    let summary = headless::run(&SimConfig::default(), &opts).unwrap();
+    assert_eq!(summary.ticks, 20);
+    assert!(summary.sim_time >= 2.0 - 1.0e-4);
+}
+
+#[test]
+fn test_same_seed_gives_same_checksum() {
+    let a = headless::run(&SimConfig::default(), &options(RunLength::Ticks(120))).unwrap();
+    let b = headless::run(&SimConfig::default(), &options(RunLength::Ticks(120))).unwrap();
+    assert_eq!(a.checksum, b.checksum);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_summary_formats() {
+    let summary = headless::run(&SimConfig::default(), &options(RunLength::Ticks(10))).unwrap();
+
+    let text = summary.render(OutputFormat::Text);
+    assert!(text.starts_with("Ran 10 ticks"));
+    assert!(text.contains("[tictactoe]"));
+    assert!(text.contains("x_wins: 0"));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&summary.render(OutputFormat::Json)).unwrap();
+    assert_eq!(json["ticks"], 10);
+    assert_eq!(json["simulations"][0]["name"], "tictactoe");
+}
+
+#[test]
+fn test_rejects_invalid_dt() {
+    let mut opts = options(RunLength::Ticks(10));
+    opts.dt = Some(0.0);
+    assert!(matches!(
+        headless::run(&SimConfig::default(), &opts),
+        Err(HeadlessError::InvalidDt(_))
+    ));
+}